@@ -0,0 +1,577 @@
+use {
+    crate::{
+        broadcast::{check_tx_response, WaitOptions},
+        client::GrpcClient,
+        definitions::BroadcastMode,
+        traits::SharedAny,
+        tx_options::TxOptions,
+        wallet::Wallet,
+        AnyResult,
+    },
+    anyhow::anyhow,
+    cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxResponse,
+    prost_types::Any,
+    std::{
+        fmt::Display,
+        ops::Range,
+        time::{Duration, Instant},
+    },
+    tokio::{
+        sync::{mpsc, oneshot},
+        task::JoinSet,
+    },
+};
+
+/// Thresholds used by [`MessageBatcher`] to flush the queued messages.
+#[derive(Clone, Debug)]
+pub struct BatchOptions {
+    /// Max number of messages in a single tx. Default is 20.
+    pub max_msgs: usize,
+    /// Max gas, as estimated by the simulations, of a single tx. Default is 5_000_000.
+    pub max_gas: u64,
+    /// Max time a message waits in the queue. Default is 2 seconds.
+    pub max_delay: Duration,
+    /// Max number of queued submissions, [`MessageBatcher::submit`] waits when full. Default is 1_000.
+    pub queue_size: usize,
+    /// Options used when waiting for the inclusion of the tx.
+    pub wait: WaitOptions,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        BatchOptions {
+            max_msgs: 20,
+            max_gas: 5_000_000,
+            max_delay: Duration::from_secs(2),
+            queue_size: 1_000,
+            wait: WaitOptions::default(),
+        }
+    }
+}
+
+impl BatchOptions {
+    pub fn max_msgs(mut self, max_msgs: usize) -> Self {
+        self.max_msgs = max_msgs;
+        self
+    }
+
+    pub fn max_gas(mut self, max_gas: u64) -> Self {
+        self.max_gas = max_gas;
+        self
+    }
+
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn queue_size(mut self, queue_size: usize) -> Self {
+        self.queue_size = queue_size;
+        self
+    }
+
+    pub fn wait(mut self, wait: WaitOptions) -> Self {
+        self.wait = wait;
+        self
+    }
+}
+
+/// Result of a submission included in a batched tx.
+#[derive(Clone, Debug)]
+pub struct BatchReceipt {
+    pub tx_response: TxResponse,
+    /// Indexes of the submitted messages inside the tx.
+    pub msg_indexes: Range<usize>,
+}
+
+/// Error returned to the producer whose message made the batched tx fail.
+///
+/// The other messages of the batch are broadcasted again without this submission.
+#[derive(Clone, Debug)]
+pub struct BatchMsgError {
+    /// Index of the failed message inside the submission.
+    pub index: usize,
+    /// Index of the failed message inside the tx.
+    pub tx_index: usize,
+    pub log: String,
+}
+
+impl Display for BatchMsgError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Message {} of the submission (tx message index {}) failed: {}",
+            self.index, self.tx_index, self.log
+        )
+    }
+}
+
+impl std::error::Error for BatchMsgError {}
+
+struct Submission {
+    msgs: Vec<Any>,
+    result: oneshot::Sender<AnyResult<BatchReceipt>>,
+}
+
+struct Pending {
+    submission: Submission,
+    gas: u64,
+}
+
+/// Collect messages from many producers and broadcast them as multi-message txs, using a background task owning the [`Wallet`].
+///
+/// Each submission is simulated when received, so failing messages are rejected before being batched.
+/// The queue is flushed when `max_msgs`, `max_gas` or `max_delay` of [`BatchOptions`] is reached.
+/// The txs are broadcasted in sync mode and their inclusion is awaited in separate tasks,
+/// so new batches are sent while the previous ones are still waiting for a block.
+/// The task stops once all the handles are dropped, after flushing the remaining messages.
+///
+/// ## Example:
+/// ``` ignore
+/// let batcher = MessageBatcher::new(wallet, BatchOptions::default().max_msgs(10));
+///
+/// let receipt = batcher.submit(vec![msg.build_any()]).await?;
+/// println!("{} {:?}", receipt.tx_response.txhash, receipt.msg_indexes);
+/// ```
+#[derive(Clone)]
+pub struct MessageBatcher {
+    sender: mpsc::Sender<Submission>,
+}
+
+impl MessageBatcher {
+    pub fn new(wallet: Wallet, options: BatchOptions) -> MessageBatcher {
+        let (sender, receiver) = mpsc::channel(options.queue_size);
+
+        tokio::spawn(run(wallet, receiver, options));
+
+        MessageBatcher { sender }
+    }
+
+    /// Queue the messages and wait until the batched tx is included in a block.
+    pub async fn submit(&self, msgs: Vec<impl SharedAny>) -> AnyResult<BatchReceipt> {
+        let (sender, receiver) = oneshot::channel();
+
+        self.sender
+            .send(Submission {
+                msgs: msgs.into_iter().map(SharedAny::into_any).collect(),
+                result: sender,
+            })
+            .await
+            .map_err(|_| anyhow!("Message batcher has been stopped"))?;
+
+        receiver
+            .await
+            .map_err(|_| anyhow!("Message batcher has been stopped"))?
+    }
+}
+
+async fn run(wallet: Wallet, mut receiver: mpsc::Receiver<Submission>, options: BatchOptions) {
+    let mut broadcaster = Broadcaster {
+        wallet,
+        wait: options.wait.clone(),
+        waits: JoinSet::new(),
+    };
+    let mut pending: Vec<Pending> = vec![];
+    let mut deadline = Instant::now();
+    let mut closed = false;
+
+    loop {
+        if closed && broadcaster.waits.is_empty() {
+            break;
+        }
+
+        let submission = tokio::select! {
+            submission = receiver.recv(), if !closed => match submission {
+                Some(submission) => submission,
+                None => {
+                    closed = true;
+                    broadcaster.flush(std::mem::take(&mut pending)).await;
+                    continue;
+                }
+            },
+            _ = tokio::time::sleep_until(deadline.into()), if !pending.is_empty() => {
+                broadcaster.flush(std::mem::take(&mut pending)).await;
+                continue;
+            }
+            Some(retry) = broadcaster.waits.join_next(), if !broadcaster.waits.is_empty() => {
+                if let Ok(batch) = retry {
+                    broadcaster.flush(batch).await;
+                }
+
+                continue;
+            }
+        };
+
+        let gas = match broadcaster
+            .wallet
+            .simulate_tx(submission.msgs.clone(), TxOptions::default())
+            .await
+        {
+            Ok(res) => res.gas_info.map(|info| info.gas_used).unwrap_or_default(),
+            Err(err) => {
+                let _ = submission.result.send(Err(err));
+                continue;
+            }
+        };
+
+        let (msgs, total_gas) = batch_size(&pending);
+
+        if !pending.is_empty()
+            && (msgs + submission.msgs.len() > options.max_msgs
+                || total_gas + gas > options.max_gas)
+        {
+            broadcaster.flush(std::mem::take(&mut pending)).await;
+        }
+
+        if pending.is_empty() {
+            deadline = Instant::now() + options.max_delay;
+        }
+
+        pending.push(Pending { submission, gas });
+
+        let (msgs, total_gas) = batch_size(&pending);
+
+        if msgs >= options.max_msgs || total_gas >= options.max_gas {
+            broadcaster.flush(std::mem::take(&mut pending)).await;
+        }
+    }
+}
+
+/// Number of messages and estimated gas of the batch.
+fn batch_size(batch: &[Pending]) -> (usize, u64) {
+    batch.iter().fold((0, 0), |(msgs, gas), pending| {
+        (msgs + pending.submission.msgs.len(), gas + pending.gas)
+    })
+}
+
+struct Broadcaster {
+    wallet: Wallet,
+    wait: WaitOptions,
+    /// Batches waiting for the inclusion, returning the submissions to broadcast again.
+    waits: JoinSet<Vec<Pending>>,
+}
+
+impl Broadcaster {
+    /// Broadcast the batch and wait for its inclusion in a separate task.
+    /// If a message fails in `CheckTx`, its submission is removed and the rest is broadcasted again.
+    async fn flush(&mut self, mut batch: Vec<Pending>) {
+        while !batch.is_empty() {
+            let msgs = batch
+                .iter()
+                .flat_map(|p| p.submission.msgs.clone())
+                .collect::<Vec<Any>>();
+
+            let result = self
+                .wallet
+                .broadcast_tx(msgs, None, None, TxOptions::default(), BroadcastMode::Sync)
+                .await
+                .and_then(|res| {
+                    res.tx_response
+                        .ok_or(anyhow!("No tx_response in broadcast response"))
+                })
+                .and_then(check_tx_response);
+
+            match result {
+                Ok(response) => {
+                    self.waits.spawn(wait_batch(
+                        self.wallet.client.clone(),
+                        response.txhash,
+                        batch,
+                        self.wait.clone(),
+                    ));
+
+                    return;
+                }
+                Err(err) => reject_failed(&mut batch, err),
+            }
+        }
+    }
+}
+
+/// Wait for the inclusion of the batched tx and resolve its submissions.
+/// If a message fails in `DeliverTx`, returns the other submissions to broadcast them again.
+async fn wait_batch(
+    client: GrpcClient,
+    txhash: String,
+    mut batch: Vec<Pending>,
+    wait: WaitOptions,
+) -> Vec<Pending> {
+    match client
+        .wait_for_tx(txhash, wait)
+        .await
+        .and_then(check_tx_response)
+    {
+        Ok(tx_response) => {
+            let mut start = 0;
+
+            for pending in batch {
+                let end = start + pending.submission.msgs.len();
+
+                let _ = pending.submission.result.send(Ok(BatchReceipt {
+                    tx_response: tx_response.clone(),
+                    msg_indexes: start..end,
+                }));
+
+                start = end;
+            }
+
+            vec![]
+        }
+        Err(err) => {
+            reject_failed(&mut batch, err);
+            batch
+        }
+    }
+}
+
+/// Remove the submission of the failed message from the batch, or the whole batch if the message is unknown.
+fn reject_failed(batch: &mut Vec<Pending>, err: anyhow::Error) {
+    let err = format!("{err:#}");
+    let failed = parse_failed_msg_index(&err).and_then(|tx_index| locate(batch, tx_index));
+
+    match failed {
+        Some((position, index, tx_index)) => {
+            let pending = batch.remove(position);
+
+            let _ = pending.submission.result.send(Err(BatchMsgError {
+                index,
+                tx_index,
+                log: err,
+            }
+            .into()));
+        }
+        None => {
+            for pending in batch.drain(..) {
+                let _ = pending.submission.result.send(Err(anyhow!("{err}")));
+            }
+        }
+    }
+}
+
+/// Find the submission containing the message at `tx_index`.
+/// Returns its position in the batch, the index of the message inside the submission and `tx_index`.
+fn locate(batch: &[Pending], tx_index: usize) -> Option<(usize, usize, usize)> {
+    let mut start = 0;
+
+    batch.iter().enumerate().find_map(|(position, pending)| {
+        let end = start + pending.submission.msgs.len();
+        let found =
+            (start..end)
+                .contains(&tx_index)
+                .then_some((position, tx_index - start, tx_index));
+
+        start = end;
+        found
+    })
+}
+
+/// Parse the index of the failed message from a tx log, e.g. `failed to execute message; message index: 1: ...`.
+fn parse_failed_msg_index(log: &str) -> Option<usize> {
+    let (_, tail) = log.split_once("message index: ")?;
+
+    tail.chars()
+        .take_while(|char| char.is_ascii_digit())
+        .collect::<String>()
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+#[allow(clippy::result_large_err)]
+mod test {
+    use {
+        super::{parse_failed_msg_index, BatchMsgError, BatchOptions, MessageBatcher},
+        crate::{
+            mock::{broadcast_response, decode_tx, mock_wallet, simulate_response},
+            ProstMsgNameToAny, WaitOptions,
+        },
+        cosmos_sdk_proto::cosmos::{
+            bank::v1beta1::MsgSend,
+            base::abci::v1beta1::TxResponse,
+            tx::v1beta1::{BroadcastTxRequest, GetTxRequest, GetTxResponse, SimulateRequest},
+        },
+        prost::Message,
+        prost_types::Any,
+        std::{
+            collections::HashMap,
+            sync::{Arc, Mutex},
+            time::Duration,
+        },
+        tonic::Status,
+    };
+
+    /// Message failing in `CheckTx` or `DeliverTx` if sent to these addresses.
+    const CHECK_TX_FAIL: &str = "check_tx_fail";
+    const DELIVER_TX_FAIL: &str = "deliver_tx_fail";
+
+    fn msg(to_address: &str) -> Any {
+        MsgSend {
+            from_address: String::new(),
+            to_address: to_address.to_string(),
+            amount: vec![],
+        }
+        .build_any()
+    }
+
+    /// Batcher over a node where each message uses 100 gas.
+    /// The txs are found by `GetTx` only after `hold` txs have been broadcasted.
+    async fn mock_batcher(hold: usize, options: BatchOptions) -> MessageBatcher {
+        let txs = Arc::new(Mutex::new(HashMap::<String, TxResponse>::new()));
+
+        let wallet = mock_wallet(move |path, _, request| {
+            let mut txs = txs.lock().unwrap();
+
+            let failed = |body_msgs: &[Any], address| {
+                body_msgs
+                    .iter()
+                    .position(|msg| {
+                        MsgSend::decode(msg.value.as_slice()).unwrap().to_address == address
+                    })
+                    .map(|index| {
+                        format!(
+                            "failed to execute message; message index: {index}: insufficient funds"
+                        )
+                    })
+            };
+
+            match path {
+                "/cosmos.tx.v1beta1.Service/Simulate" => {
+                    let (body, _) = decode_tx(&SimulateRequest::decode(request).unwrap().tx_bytes);
+
+                    Ok(simulate_response(100 * body.messages.len() as u64))
+                }
+                "/cosmos.tx.v1beta1.Service/BroadcastTx" => {
+                    let (body, _) =
+                        decode_tx(&BroadcastTxRequest::decode(request).unwrap().tx_bytes);
+
+                    if let Some(log) = failed(&body.messages, CHECK_TX_FAIL) {
+                        return Ok(broadcast_response("", 5, &log));
+                    }
+
+                    let txhash = format!("TX{}", txs.len());
+                    let log = failed(&body.messages, DELIVER_TX_FAIL);
+
+                    txs.insert(
+                        txhash.clone(),
+                        TxResponse {
+                            txhash: txhash.clone(),
+                            codespace: if log.is_some() { "sdk" } else { "" }.to_string(),
+                            code: if log.is_some() { 5 } else { 0 },
+                            raw_log: log.unwrap_or_default(),
+                            ..Default::default()
+                        },
+                    );
+
+                    Ok(broadcast_response(&txhash, 0, ""))
+                }
+                "/cosmos.tx.v1beta1.Service/GetTx" => {
+                    let hash = GetTxRequest::decode(request).unwrap().hash;
+
+                    match txs.get(&hash).filter(|_| txs.len() >= hold) {
+                        Some(tx_response) => Ok(GetTxResponse {
+                            tx: None,
+                            tx_response: Some(tx_response.clone()),
+                        }
+                        .encode_to_vec()),
+                        None => Err(Status::not_found(format!("tx {hash} not found"))),
+                    }
+                }
+                _ => Err(Status::unimplemented(path.to_string())),
+            }
+        })
+        .await;
+
+        MessageBatcher::new(
+            wallet,
+            options.wait(
+                WaitOptions::default()
+                    .poll_interval(Duration::from_millis(10))
+                    .timeout(Duration::from_secs(2)),
+            ),
+        )
+    }
+
+    #[tokio::test]
+    async fn flush_thresholds() {
+        let never = Duration::from_secs(3600);
+
+        let batcher = mock_batcher(0, BatchOptions::default().max_msgs(2).max_delay(never)).await;
+        let (first, second) = tokio::join!(
+            batcher.submit(vec![msg("a")]),
+            batcher.submit(vec![msg("b")])
+        );
+        let (first, second) = (first.unwrap(), second.unwrap());
+        assert_eq!(first.tx_response.txhash, second.tx_response.txhash);
+        assert_eq!((first.msg_indexes, second.msg_indexes), (0..1, 1..2));
+
+        let batcher = mock_batcher(
+            0,
+            BatchOptions::default()
+                .max_gas(300)
+                .max_msgs(100)
+                .max_delay(never),
+        )
+        .await;
+        let (first, second) = tokio::join!(
+            batcher.submit(vec![msg("a")]),
+            batcher.submit(vec![msg("b"), msg("c")])
+        );
+        let (first, second) = (first.unwrap(), second.unwrap());
+        assert_eq!(first.tx_response.txhash, second.tx_response.txhash);
+        assert_eq!((first.msg_indexes, second.msg_indexes), (0..1, 1..3));
+
+        let batcher = mock_batcher(
+            0,
+            BatchOptions::default().max_delay(Duration::from_millis(50)),
+        )
+        .await;
+        let receipt = batcher.submit(vec![msg("a")]).await.unwrap();
+        assert_eq!(receipt.msg_indexes, 0..1);
+    }
+
+    #[tokio::test]
+    async fn failed_submissions() {
+        let batcher = mock_batcher(0, BatchOptions::default().max_msgs(4)).await;
+
+        let (ok, check_tx_fail, deliver_tx_fail) = tokio::join!(
+            batcher.submit(vec![msg("a")]),
+            batcher.submit(vec![msg("b"), msg(CHECK_TX_FAIL)]),
+            batcher.submit(vec![msg(DELIVER_TX_FAIL)]),
+        );
+
+        // Dropped from the first tx, rejected in `CheckTx`
+        let err = check_tx_fail.unwrap_err();
+        let err = err.downcast_ref::<BatchMsgError>().unwrap();
+        assert_eq!((err.index, err.tx_index), (1, 2));
+
+        // Dropped from the second tx, `TX0` failed in `DeliverTx`
+        let err = deliver_tx_fail.unwrap_err();
+        let err = err.downcast_ref::<BatchMsgError>().unwrap();
+        assert_eq!((err.index, err.tx_index), (0, 1));
+
+        let receipt = ok.unwrap();
+        assert_eq!(receipt.tx_response.txhash, "TX1");
+        assert_eq!(receipt.msg_indexes, 0..1);
+    }
+
+    #[tokio::test]
+    async fn broadcast_while_waiting() {
+        // The first tx is included only after the second one is broadcasted
+        let batcher = mock_batcher(2, BatchOptions::default().max_msgs(1)).await;
+
+        let (first, second) = tokio::join!(
+            batcher.submit(vec![msg("a")]),
+            batcher.submit(vec![msg("b")])
+        );
+        assert_eq!(first.unwrap().tx_response.txhash, "TX0");
+        assert_eq!(second.unwrap().tx_response.txhash, "TX1");
+    }
+
+    #[test]
+    fn failed_msg_index() {
+        let log = "Tx 0A1B failed with code 5 (codespace: sdk): failed to execute message; message index: 3: insufficient funds";
+
+        assert_eq!(parse_failed_msg_index(log), Some(3));
+        assert_eq!(parse_failed_msg_index("out of gas"), None);
+    }
+}
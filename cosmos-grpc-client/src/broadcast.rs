@@ -0,0 +1,237 @@
+use {
+    crate::{
+        client::GrpcClient, definitions::BroadcastMode, errors::Error, traits::SharedAny,
+        tx_options::TxOptions, wallet::Wallet, AnyResult,
+    },
+    anyhow::{anyhow, bail},
+    cosmos_sdk_proto::cosmos::{base::abci::v1beta1::TxResponse, tx::v1beta1::GetTxRequest},
+    cosmrs::tx::Fee,
+    std::time::{Duration, Instant},
+    tonic::Code,
+};
+
+/// Options used when waiting for a transaction to be included in a block.
+#[derive(Clone, Debug)]
+pub struct WaitOptions {
+    /// Max time to wait for the inclusion. Default is 60 seconds.
+    pub timeout: Duration,
+    /// Interval between two `GetTx` requests. Default is 1 second.
+    pub poll_interval: Duration,
+    /// Stop waiting once the chain is past this height, the tx can still be included in the block at `timeout_height`.
+    pub timeout_height: Option<u64>,
+}
+
+impl Default for WaitOptions {
+    fn default() -> Self {
+        WaitOptions {
+            timeout: Duration::from_secs(60),
+            poll_interval: Duration::from_secs(1),
+            timeout_height: None,
+        }
+    }
+}
+
+impl WaitOptions {
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    pub fn timeout_height(mut self, height: u64) -> Self {
+        self.timeout_height = Some(height);
+        self
+    }
+}
+
+/// Return an [`Error::ChainRejection`] if the tx has been rejected (`code != 0`).
+pub fn check_tx_response(response: TxResponse) -> AnyResult<TxResponse> {
+    if let Some(err) = Error::from_tx_response(&response) {
+        return Err(err.into());
+    }
+
+    Ok(response)
+}
+
+impl GrpcClient {
+    /// Poll `GetTx` until the tx `hash` is included in a block, or the timeout of `options` is reached.
+    ///
+    /// The [`TxResponse`] is returned also if the tx failed, use [`check_tx_response`] to check its `code`.
+    pub async fn wait_for_tx(
+        &self,
+        hash: impl Into<String>,
+        options: WaitOptions,
+    ) -> AnyResult<TxResponse> {
+        let hash: String = hash.into();
+        let start = Instant::now();
+        let mut tx_client = self.clients.tx.clone();
+
+        loop {
+            // Read before querying the tx, so that a tx included up to this height is found by `GetTx`
+            let height = match options.timeout_height {
+                Some(_) => Some(self.latest_height().await?),
+                None => None,
+            };
+
+            match tx_client.get_tx(GetTxRequest { hash: hash.clone() }).await {
+                Ok(res) => {
+                    if let Some(tx_response) = res.into_inner().tx_response {
+                        return Ok(tx_response);
+                    }
+                }
+                // The tx is not indexed yet
+                Err(status) if status.code() == Code::NotFound => {}
+                Err(status) if status.message().contains("not found") => {}
+                Err(status) => return Err(status.into()),
+            }
+
+            if start.elapsed() + options.poll_interval > options.timeout {
+                bail!("Tx {hash} not included after {:?}", options.timeout)
+            }
+
+            // The tx can still be included in the block at `timeout_height`
+            if let (Some(height), Some(timeout_height)) = (height, options.timeout_height) {
+                if height > timeout_height {
+                    bail!("Tx {hash} not included up to height {timeout_height}")
+                }
+            }
+
+            tokio::time::sleep(options.poll_interval).await;
+        }
+    }
+}
+
+impl Wallet {
+    /// Broadcast a tx and wait for its inclusion in a block.
+    ///
+    /// Replaces `BroadcastMode::Block`, removed from sdk 0.47.
+    /// Returns an error containing codespace and raw log if the tx fails in `CheckTx` or `DeliverTx`.
+    /// If not set in `options`, the wait stops at the timeout height of `tx_options`.
+    pub async fn broadcast_tx_and_wait(
+        &mut self,
+        msgs: Vec<impl SharedAny>,
+        fee: Option<Fee>,
+        memo: Option<String>,
+        tx_options: TxOptions,
+        mut options: WaitOptions,
+    ) -> AnyResult<TxResponse> {
+        let tx_options = tx_options.resolve(&self.client).await?;
+
+        if options.timeout_height.is_none() {
+            options.timeout_height = tx_options.timeout_height;
+        }
+
+        let response = self
+            .broadcast_tx(msgs, fee, memo, tx_options, BroadcastMode::Sync)
+            .await?
+            .tx_response
+            .ok_or(anyhow!("No tx_response in broadcast response"))?;
+
+        let response = check_tx_response(response)?;
+
+        check_tx_response(self.client.wait_for_tx(response.txhash, options).await?)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::result_large_err)]
+mod test {
+    use {
+        super::WaitOptions,
+        crate::{
+            mock::{latest_block, mock_client},
+            GrpcClient,
+        },
+        cosmos_sdk_proto::cosmos::{
+            base::abci::v1beta1::TxResponse,
+            tx::v1beta1::{GetTxRequest, GetTxResponse},
+        },
+        prost::Message,
+        std::{
+            sync::{
+                atomic::{AtomicI64, Ordering},
+                Arc,
+            },
+            time::Duration,
+        },
+        tonic::Status,
+    };
+
+    /// Node producing a block on each `GetLatestBlock`, starting from height 10.
+    /// `PENDING` is indexed after 2 `GetTx`, `LATE` is included at height 12 and `MISSING` is never found.
+    async fn mock_tx_node() -> GrpcClient {
+        let height = Arc::new(AtomicI64::new(9));
+        let get_tx_calls = Arc::new(AtomicI64::new(0));
+
+        mock_client(move |path, _, request| match path {
+            "/cosmos.base.tendermint.v1beta1.Service/GetLatestBlock" => {
+                Ok(latest_block(height.fetch_add(1, Ordering::SeqCst) + 1))
+            }
+            "/cosmos.tx.v1beta1.Service/GetTx" => {
+                let hash = GetTxRequest::decode(request).unwrap().hash;
+
+                let found = match hash.as_str() {
+                    "PENDING" => get_tx_calls.fetch_add(1, Ordering::SeqCst) >= 2,
+                    "LATE" => height.load(Ordering::SeqCst) >= 12,
+                    _ => false,
+                };
+
+                if !found {
+                    return Err(Status::not_found(format!("tx not found: {hash}")));
+                }
+
+                Ok(GetTxResponse {
+                    tx: None,
+                    tx_response: Some(TxResponse {
+                        txhash: hash,
+                        ..Default::default()
+                    }),
+                }
+                .encode_to_vec())
+            }
+            _ => Err(Status::unimplemented(path.to_string())),
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn wait_for_tx() {
+        let options = WaitOptions::default().poll_interval(Duration::from_millis(10));
+
+        // Polled until indexed
+        let client = mock_tx_node().await;
+        let response = client
+            .wait_for_tx("PENDING", options.clone())
+            .await
+            .unwrap();
+        assert_eq!(response.txhash, "PENDING");
+
+        // Included in the block at the timeout height
+        let client = mock_tx_node().await;
+        let response = client
+            .wait_for_tx("LATE", options.clone().timeout_height(12))
+            .await
+            .unwrap();
+        assert_eq!(response.txhash, "LATE");
+
+        // The chain is past the timeout height
+        let client = mock_tx_node().await;
+        let err = client
+            .wait_for_tx("MISSING", options.clone().timeout_height(12))
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Tx MISSING not included up to height 12");
+
+        // Wall-clock timeout
+        let client = mock_tx_node().await;
+        let err = client
+            .wait_for_tx("MISSING", options.timeout(Duration::from_millis(100)))
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Tx MISSING not included after 100ms");
+    }
+}
@@ -0,0 +1,431 @@
+use {
+    crate::{
+        channel::{check_health, spawn_health_check, GrpcChannel, HealthCheckOptions},
+        client::GrpcClient,
+        AnyResult,
+    },
+    anyhow::{anyhow, bail},
+    cosmos_sdk_proto::cosmos::base::tendermint::v1beta1::{
+        service_client::ServiceClient as TendermintClient, GetNodeInfoRequest,
+    },
+    std::time::Duration,
+    tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Identity},
+};
+
+/// Default max size of a decoded message used by `tonic` (4 MiB).
+pub const DEFAULT_MAX_DECODING_MESSAGE_SIZE: usize = 4 * 1024 * 1024;
+
+/// Message size limits applied to every client created by [`GrpcClient`].
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct MessageLimits {
+    pub max_decoding_message_size: usize,
+    pub max_encoding_message_size: usize,
+}
+
+impl Default for MessageLimits {
+    fn default() -> Self {
+        MessageLimits {
+            max_decoding_message_size: DEFAULT_MAX_DECODING_MESSAGE_SIZE,
+            max_encoding_message_size: usize::MAX,
+        }
+    }
+}
+
+/// Builder for [`GrpcClient`], exposing the transport options of the underlying `tonic` channel.
+///
+/// ## Example:
+/// ``` ignore
+/// let client = GrpcClientBuilder::new("https://grpc.osmosis.zone:9090")
+///     .connect_timeout(Duration::from_secs(5))
+///     .timeout(Duration::from_secs(30))
+///     .http2_keep_alive_interval(Duration::from_secs(20))
+///     .max_decoding_message_size(64 * 1024 * 1024)
+///     .build()
+///     .await
+///     .unwrap();
+/// ```
+///
+/// When more than one endpoint is provided, the nodes are periodically checked and requests
+/// are routed to a healthy and up-to-date node, falling back to the next one on transport errors:
+/// ``` ignore
+/// let client = GrpcClientBuilder::new(OSMOSIS_GRPC_MAINNET)
+///     .endpoint("https://grpc.osmosis.zone:9090")
+///     .health_check_interval(Duration::from_secs(5))
+///     .max_block_lag(3)
+///     .build()
+///     .await
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct GrpcClientBuilder {
+    addresses: Vec<String>,
+    chain_id: Option<String>,
+    health_check: HealthCheckOptions,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    tcp_keepalive: Option<Duration>,
+    http2_keep_alive_interval: Option<Duration>,
+    keep_alive_timeout: Option<Duration>,
+    keep_alive_while_idle: Option<bool>,
+    tls: Option<ClientTlsConfig>,
+    limits: MessageLimits,
+}
+
+impl GrpcClientBuilder {
+    pub fn new(grpc_address: impl Into<String>) -> GrpcClientBuilder {
+        GrpcClientBuilder::with_addresses(vec![grpc_address.into()])
+    }
+
+    /// Create a builder from a list of endpoints, in order of preference. Fails if the list is empty.
+    pub fn from_endpoints(grpc_addresses: Vec<String>) -> AnyResult<GrpcClientBuilder> {
+        if grpc_addresses.is_empty() {
+            bail!("No grpc endpoint provided");
+        }
+
+        Ok(GrpcClientBuilder::with_addresses(grpc_addresses))
+    }
+
+    fn with_addresses(addresses: Vec<String>) -> GrpcClientBuilder {
+        GrpcClientBuilder {
+            addresses,
+            chain_id: None,
+            health_check: HealthCheckOptions::default(),
+            connect_timeout: None,
+            timeout: None,
+            tcp_keepalive: None,
+            http2_keep_alive_interval: None,
+            keep_alive_timeout: None,
+            keep_alive_while_idle: None,
+            tls: None,
+            limits: MessageLimits::default(),
+        }
+    }
+
+    /// Add a fallback endpoint.
+    pub fn endpoint(mut self, grpc_address: impl Into<String>) -> Self {
+        self.addresses.push(grpc_address.into());
+        self
+    }
+
+    /// Expected `chain_id`. Endpoints reporting a different network are rejected.
+    ///
+    /// If not set, the network of the first reachable endpoint is used.
+    pub fn chain_id(mut self, chain_id: impl Into<String>) -> Self {
+        self.chain_id = Some(chain_id.into());
+        self
+    }
+
+    /// Interval between two health checks of the endpoints. Default is 10 seconds.
+    pub fn health_check_interval(mut self, interval: Duration) -> Self {
+        self.health_check.interval = interval;
+        self
+    }
+
+    /// Timeout of a single health check. Default is 5 seconds.
+    pub fn health_check_timeout(mut self, timeout: Duration) -> Self {
+        self.health_check.probe_timeout = timeout;
+        self
+    }
+
+    /// Max number of blocks a node can be behind the best node to be considered healthy. Default is 5.
+    pub fn max_block_lag(mut self, blocks: u64) -> Self {
+        self.health_check.max_block_lag = blocks;
+        self
+    }
+
+    /// Timeout applied when connecting to the node.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Timeout applied to each request.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enable TCP keepalive with the given interval.
+    pub fn tcp_keepalive(mut self, interval: Duration) -> Self {
+        self.tcp_keepalive = Some(interval);
+        self
+    }
+
+    /// Interval of the HTTP/2 keepalive pings.
+    pub fn http2_keep_alive_interval(mut self, interval: Duration) -> Self {
+        self.http2_keep_alive_interval = Some(interval);
+        self
+    }
+
+    /// Timeout for the HTTP/2 keepalive ping acknowledgement.
+    pub fn keep_alive_timeout(mut self, timeout: Duration) -> Self {
+        self.keep_alive_timeout = Some(timeout);
+        self
+    }
+
+    /// Send HTTP/2 keepalive pings also when no request is in flight.
+    pub fn keep_alive_while_idle(mut self, enabled: bool) -> Self {
+        self.keep_alive_while_idle = Some(enabled);
+        self
+    }
+
+    /// Custom CA certificate (PEM encoded) used to verify the node certificate.
+    pub fn ca_certificate(mut self, pem: impl AsRef<[u8]>) -> Self {
+        self.tls = Some(
+            self.tls
+                .unwrap_or_default()
+                .ca_certificate(Certificate::from_pem(pem)),
+        );
+        self
+    }
+
+    /// Client certificate and private key (PEM encoded) used for mutual TLS.
+    pub fn identity(mut self, cert_pem: impl AsRef<[u8]>, key_pem: impl AsRef<[u8]>) -> Self {
+        self.tls = Some(
+            self.tls
+                .unwrap_or_default()
+                .identity(Identity::from_pem(cert_pem, key_pem)),
+        );
+        self
+    }
+
+    /// Domain name used to verify the node certificate, if different from the address host.
+    pub fn tls_domain_name(mut self, domain_name: impl Into<String>) -> Self {
+        self.tls = Some(self.tls.unwrap_or_default().domain_name(domain_name));
+        self
+    }
+
+    /// Max size of a decoded response. Default is 4 MiB, increase it for large queries like `AllContractState`.
+    pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+        self.limits.max_decoding_message_size = limit;
+        self
+    }
+
+    /// Max size of an encoded request. Default is `usize::MAX`.
+    pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+        self.limits.max_encoding_message_size = limit;
+        self
+    }
+
+    /// Connect to the node(s) and build the [`GrpcClient`].
+    pub async fn build(self) -> AnyResult<GrpcClient> {
+        match self.addresses.as_slice() {
+            [address] => {
+                let channel = self.tonic_endpoint(address)?.connect().await?;
+
+                let client =
+                    GrpcClient::build(GrpcChannel::single(address, channel), self.limits).await?;
+
+                if let Some(chain_id) = &self.chain_id {
+                    if &client.chain_id != chain_id {
+                        return Err(anyhow!(
+                            "Endpoint {address} is on network {}, expected {chain_id}",
+                            client.chain_id
+                        ));
+                    }
+                }
+
+                Ok(client)
+            }
+            addresses => {
+                let mut nodes = vec![];
+                let mut networks = vec![];
+
+                for address in addresses {
+                    let channel = self.tonic_endpoint(address)?.connect_lazy();
+
+                    let network = tokio::time::timeout(
+                        self.health_check.probe_timeout,
+                        TendermintClient::new(channel.clone()).get_node_info(GetNodeInfoRequest {}),
+                    )
+                    .await
+                    .ok()
+                    .and_then(|res| res.ok())
+                    .and_then(|res| res.into_inner().default_node_info)
+                    .map(|info| info.network);
+
+                    networks.push(network);
+                    nodes.push((address.clone(), channel));
+                }
+
+                let chain_id = self
+                    .chain_id
+                    .clone()
+                    .or(networks.iter().flatten().next().cloned())
+                    .ok_or(anyhow!("No reachable grpc endpoint"))?;
+
+                if !networks
+                    .iter()
+                    .flatten()
+                    .any(|network| network == &chain_id)
+                {
+                    return Err(anyhow!("No reachable grpc endpoint on network {chain_id}"));
+                }
+
+                let channel = GrpcChannel::multi(nodes);
+
+                check_health(&channel, &chain_id, self.health_check).await;
+
+                spawn_health_check(&channel, chain_id, self.health_check);
+
+                GrpcClient::build(channel, self.limits).await
+            }
+        }
+    }
+
+    fn tonic_endpoint(&self, address: &str) -> AnyResult<Endpoint> {
+        let mut endpoint = Channel::builder(address.parse()?);
+
+        if let Some(timeout) = self.connect_timeout {
+            endpoint = endpoint.connect_timeout(timeout);
+        }
+
+        if let Some(timeout) = self.timeout {
+            endpoint = endpoint.timeout(timeout);
+        }
+
+        if let Some(interval) = self.tcp_keepalive {
+            endpoint = endpoint.tcp_keepalive(Some(interval));
+        }
+
+        if let Some(interval) = self.http2_keep_alive_interval {
+            endpoint = endpoint.http2_keep_alive_interval(interval);
+        }
+
+        if let Some(timeout) = self.keep_alive_timeout {
+            endpoint = endpoint.keep_alive_timeout(timeout);
+        }
+
+        if let Some(enabled) = self.keep_alive_while_idle {
+            endpoint = endpoint.keep_alive_while_idle(enabled);
+        }
+
+        if let Some(tls) = self.tls.clone() {
+            endpoint = endpoint.tls_config(tls)?;
+        }
+
+        Ok(endpoint)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::result_large_err)]
+mod test {
+    use {
+        crate::{
+            mock::{mock_node, MOCK_CHAIN_ID},
+            GrpcClientBuilder,
+        },
+        cosmos_sdk_proto::{
+            cosmos::{
+                bank::v1beta1::{QueryBalanceRequest, QueryBalanceResponse},
+                base::{tendermint::v1beta1::GetNodeInfoResponse, v1beta1::Coin},
+            },
+            tendermint::v0_34::p2p::DefaultNodeInfo,
+        },
+        prost::Message,
+        std::{
+            sync::{
+                atomic::{AtomicUsize, Ordering},
+                Arc,
+            },
+            time::{Duration, Instant},
+        },
+        tonic::{Code, Status},
+    };
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn builder_options() {
+        assert!(GrpcClientBuilder::from_endpoints(vec![]).is_err());
+
+        let max_denom_len = Arc::new(AtomicUsize::new(0));
+
+        let address = mock_node({
+            let max_denom_len = max_denom_len.clone();
+            move |path, _, request| match path {
+                "/cosmos.base.tendermint.v1beta1.Service/GetNodeInfo" => Ok(GetNodeInfoResponse {
+                    default_node_info: Some(DefaultNodeInfo {
+                        network: MOCK_CHAIN_ID.to_string(),
+                        ..Default::default()
+                    }),
+                    application_version: None,
+                }
+                .encode_to_vec()),
+                "/cosmos.bank.v1beta1.Query/Balance" => {
+                    let request = QueryBalanceRequest::decode(request).unwrap();
+                    max_denom_len.fetch_max(request.denom.len(), Ordering::SeqCst);
+
+                    let amount = match request.denom.as_str() {
+                        "large" => "1".repeat(2000),
+                        "slow" => {
+                            tokio::task::block_in_place(|| {
+                                std::thread::sleep(Duration::from_millis(500))
+                            });
+                            "1".to_string()
+                        }
+                        _ => "1".to_string(),
+                    };
+
+                    Ok(QueryBalanceResponse {
+                        balance: Some(Coin {
+                            denom: request.denom,
+                            amount,
+                        }),
+                    }
+                    .encode_to_vec())
+                }
+                _ => Err(Status::unimplemented(path)),
+            }
+        });
+
+        let client = GrpcClientBuilder::from_endpoints(vec![address])
+            .unwrap()
+            .timeout(Duration::from_millis(100))
+            .max_decoding_message_size(1024)
+            .max_encoding_message_size(1024)
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(client.chain_id, MOCK_CHAIN_ID);
+
+        let balance = |denom: &str| {
+            let mut bank = client.clients.bank.clone();
+            let request = QueryBalanceRequest {
+                address: "osmo1".to_string(),
+                denom: denom.to_string(),
+            };
+            async move { bank.balance(request).await }
+        };
+
+        assert!(balance("uosmo").await.is_ok());
+
+        // Response larger than the decoding limit, on the standard clients and `proto_query`
+        assert_eq!(balance("large").await.unwrap_err().code(), Code::OutOfRange);
+
+        let err = client
+            .proto_query::<_, QueryBalanceResponse>(
+                QueryBalanceRequest {
+                    address: "osmo1".to_string(),
+                    denom: "large".to_string(),
+                },
+                "/cosmos.bank.v1beta1.Query/Balance",
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("message length too large"),
+            "{err}"
+        );
+
+        // Request timeout
+        let start = Instant::now();
+        assert_eq!(balance("slow").await.unwrap_err().code(), Code::Cancelled);
+        assert!(start.elapsed() < Duration::from_millis(500));
+
+        // Request larger than the encoding limit never reaches the node,
+        // checked last since the node closes the connection
+        assert!(balance(&"u".repeat(2000)).await.is_err());
+        assert!(max_denom_len.load(Ordering::SeqCst) < 2000);
+    }
+}
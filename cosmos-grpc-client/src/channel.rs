@@ -0,0 +1,528 @@
+use {
+    crate::AnyResult,
+    anyhow::anyhow,
+    cosmos_sdk_proto::cosmos::base::tendermint::v1beta1::{
+        service_client::ServiceClient as TendermintClient, GetLatestBlockRequest,
+        GetLatestBlockResponse, GetSyncingRequest,
+    },
+    futures::future::poll_fn,
+    http_body::Body as HttpBody,
+    std::{
+        future::Future,
+        pin::Pin,
+        sync::{Arc, RwLock, Weak},
+        task::{Context, Poll},
+        time::Duration,
+    },
+    tonic::{
+        body::BoxBody,
+        codegen::{http, Bytes, Service},
+        transport::{Body, Channel},
+    },
+};
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Metadata key used by cosmos-sdk to select the height of a query, also returned in the response.
+pub const BLOCK_HEIGHT_METADATA: &str = "x-cosmos-block-height";
+
+/// Health of a single node as seen by the last probe.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EndpointHealth {
+    /// Not probed yet.
+    Unknown,
+    /// Reachable, not syncing and close to the highest known height.
+    Healthy,
+    /// Node is catching up.
+    Syncing,
+    /// Node is reachable but more than `max_block_lag` blocks behind the best node.
+    Behind { lag: u64 },
+    /// Probe or request failed with a transport error.
+    Unreachable { error: String },
+    /// Node reports a different `chain_id`. The endpoint is never used.
+    WrongNetwork { chain_id: String },
+}
+
+#[derive(Clone, Debug)]
+pub struct EndpointStatus {
+    pub address: String,
+    pub health: EndpointHealth,
+    pub latest_height: Option<u64>,
+}
+
+struct Node {
+    address: String,
+    channel: Channel,
+}
+
+struct Shared {
+    nodes: Vec<Node>,
+    status: RwLock<Vec<EndpointStatus>>,
+}
+
+/// Service used by every client of [`crate::GrpcClient`].
+///
+/// Requests are routed to the first healthy node, following the order the endpoints have been provided.
+/// On transport error the request is sent again to the next available node.
+///
+/// If a height is set, every request is performed against the state at that height,
+/// preferring the nodes that have already reached it.
+#[derive(Clone)]
+pub struct GrpcChannel {
+    shared: Arc<Shared>,
+    height: Option<u64>,
+    node: Option<usize>,
+}
+
+impl GrpcChannel {
+    pub(crate) fn single(address: impl Into<String>, channel: Channel) -> GrpcChannel {
+        GrpcChannel::multi(vec![(address.into(), channel)])
+    }
+
+    pub(crate) fn multi(nodes: Vec<(String, Channel)>) -> GrpcChannel {
+        let status = nodes
+            .iter()
+            .map(|(address, _)| EndpointStatus {
+                address: address.clone(),
+                health: EndpointHealth::Unknown,
+                latest_height: None,
+            })
+            .collect();
+
+        GrpcChannel {
+            shared: Arc::new(Shared {
+                nodes: nodes
+                    .into_iter()
+                    .map(|(address, channel)| Node { address, channel })
+                    .collect(),
+                status: RwLock::new(status),
+            }),
+            height: None,
+            node: None,
+        }
+    }
+
+    /// Clone the channel, pinning every request to the state at `height`.
+    pub fn at_height(&self, height: u64) -> GrpcChannel {
+        GrpcChannel {
+            shared: self.shared.clone(),
+            height: Some(height),
+            node: self.node,
+        }
+    }
+
+    /// Clone the channel, sending every request to the node currently preferred by the routing.
+    ///
+    /// There is no fallback to the other nodes, requests fail if the node is unreachable.
+    pub(crate) fn pin_node(&self) -> GrpcChannel {
+        GrpcChannel {
+            shared: self.shared.clone(),
+            height: self.height,
+            node: self.routing_order().first().copied(),
+        }
+    }
+
+    /// Height every request is pinned to, if any.
+    pub fn height(&self) -> Option<u64> {
+        self.height
+    }
+
+    /// Current status of every endpoint.
+    pub fn endpoints_status(&self) -> Vec<EndpointStatus> {
+        self.shared.status.read().unwrap().clone()
+    }
+
+    pub(crate) fn node_channels(&self) -> Vec<(String, Channel)> {
+        self.shared
+            .nodes
+            .iter()
+            .map(|node| (node.address.clone(), node.channel.clone()))
+            .collect()
+    }
+
+    pub(crate) fn set_health(&self, index: usize, health: EndpointHealth) {
+        self.shared.status.write().unwrap()[index].health = health;
+    }
+
+    /// Indexes of the nodes to try, healthy ones first.
+    ///
+    /// If a height is set, the nodes last seen below it are tried last,
+    /// since they would fail reporting the height as not available.
+    fn routing_order(&self) -> Vec<usize> {
+        if let Some(node) = self.node {
+            return vec![node];
+        }
+
+        let status = self.shared.status.read().unwrap();
+
+        let (mut preferred, fallback): (Vec<usize>, Vec<usize>) = (0..status.len())
+            .filter(|i| !matches!(status[*i].health, EndpointHealth::WrongNetwork { .. }))
+            .partition(|i| status[*i].health == EndpointHealth::Healthy);
+
+        preferred.extend(fallback);
+
+        if let Some(height) = self.height {
+            let (behind, mut reached): (Vec<usize>, Vec<usize>) =
+                preferred.into_iter().partition(|i| {
+                    status[*i]
+                        .latest_height
+                        .is_some_and(|latest_height| latest_height < height)
+                });
+
+            reached.extend(behind);
+            return reached;
+        }
+
+        preferred
+    }
+
+    async fn dispatch(
+        self,
+        mut request: http::Request<BoxBody>,
+    ) -> Result<http::Response<Body>, BoxError> {
+        if let Some(height) = self.height {
+            request
+                .headers_mut()
+                .insert(BLOCK_HEIGHT_METADATA, http::HeaderValue::from(height));
+        }
+
+        let order = self.routing_order();
+
+        if order.len() <= 1 {
+            let index = *order.first().ok_or("No endpoint available")?;
+            let channel = self.shared.nodes[index].channel.clone();
+            return Ok(send(channel, request).await?);
+        }
+
+        // The body has to be buffered in order to send the same request to more than one node
+        let (parts, mut body) = request.into_parts();
+        let mut buffer: Vec<u8> = vec![];
+        while let Some(chunk) = body.data().await {
+            buffer.extend_from_slice(&chunk?);
+        }
+        let body = Bytes::from(buffer);
+
+        let mut last_error: Option<BoxError> = None;
+
+        for index in order {
+            let mut request = http::Request::builder()
+                .method(parts.method.clone())
+                .uri(parts.uri.clone())
+                .version(parts.version)
+                .body(
+                    http_body::Full::new(body.clone())
+                        .map_err(|never| match never {})
+                        .boxed_unsync(),
+                )?;
+            *request.headers_mut() = parts.headers.clone();
+
+            let channel = self.shared.nodes[index].channel.clone();
+
+            match send(channel, request).await {
+                Ok(response) => return Ok(response),
+                Err(err) => {
+                    self.set_health(
+                        index,
+                        EndpointHealth::Unreachable {
+                            error: err.to_string(),
+                        },
+                    );
+                    last_error = Some(err.into());
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| "No endpoint available".into()))
+    }
+}
+
+/// Send `request` to `channel`, waiting for the buffer of the channel to have capacity.
+async fn send(
+    mut channel: Channel,
+    request: http::Request<BoxBody>,
+) -> Result<http::Response<Body>, tonic::transport::Error> {
+    poll_fn(|cx| channel.poll_ready(cx)).await?;
+    channel.call(request).await
+}
+
+impl Service<http::Request<BoxBody>> for GrpcChannel {
+    type Response = http::Response<Body>;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // The node channels are polled for readiness by `send`, once a node has been selected
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<BoxBody>) -> Self::Future {
+        Box::pin(self.clone().dispatch(request))
+    }
+}
+
+/// Options of the background health check used when more than one endpoint is provided.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct HealthCheckOptions {
+    pub interval: Duration,
+    pub probe_timeout: Duration,
+    pub max_block_lag: u64,
+}
+
+impl Default for HealthCheckOptions {
+    fn default() -> Self {
+        HealthCheckOptions {
+            interval: Duration::from_secs(10),
+            probe_timeout: Duration::from_secs(5),
+            max_block_lag: 5,
+        }
+    }
+}
+
+/// Extract height and `chain_id` from the latest block.
+pub(crate) fn latest_block_header(res: GetLatestBlockResponse) -> AnyResult<(u64, String)> {
+    // `sdk_block` is only returned by sdk 0.47+
+    res.sdk_block
+        .and_then(|block| block.header)
+        .map(|header| (header.height as u64, header.chain_id))
+        .or(res
+            .block
+            .and_then(|block| block.header)
+            .map(|header| (header.height as u64, header.chain_id)))
+        .ok_or(anyhow!("No header in latest block"))
+}
+
+struct Probe {
+    syncing: bool,
+    height: u64,
+    chain_id: String,
+}
+
+async fn probe(channel: Channel) -> AnyResult<Probe> {
+    let mut client = TendermintClient::new(channel);
+
+    let syncing = client
+        .get_syncing(GetSyncingRequest {})
+        .await?
+        .into_inner()
+        .syncing;
+
+    let res = client
+        .get_latest_block(GetLatestBlockRequest {})
+        .await?
+        .into_inner();
+
+    let (height, chain_id) = latest_block_header(res)?;
+
+    Ok(Probe {
+        syncing,
+        height,
+        chain_id,
+    })
+}
+
+/// Probe every node once and update the status of the channel.
+pub(crate) async fn check_health(
+    channel: &GrpcChannel,
+    chain_id: &str,
+    options: HealthCheckOptions,
+) {
+    let probes = channel
+        .node_channels()
+        .into_iter()
+        .map(|(_, node)| {
+            tokio::spawn(async move {
+                tokio::time::timeout(options.probe_timeout, probe(node))
+                    .await
+                    .map_err(|_| anyhow!("Health check timed out"))
+                    .and_then(|res| res)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut results = vec![];
+    for probe in probes {
+        results.push(
+            probe
+                .await
+                .map_err(|err| anyhow!("{err}"))
+                .and_then(|res| res),
+        );
+    }
+
+    let best_height = results
+        .iter()
+        .filter_map(|res| res.as_ref().ok())
+        .filter(|probe| probe.chain_id == chain_id)
+        .map(|probe| probe.height)
+        .max()
+        .unwrap_or_default();
+
+    let mut status = channel.shared.status.write().unwrap();
+
+    for (status, result) in status.iter_mut().zip(results) {
+        if let EndpointHealth::WrongNetwork { .. } = status.health {
+            continue;
+        }
+
+        match result {
+            Ok(probe) => {
+                status.latest_height = Some(probe.height);
+                status.health = if probe.chain_id != chain_id {
+                    EndpointHealth::WrongNetwork {
+                        chain_id: probe.chain_id,
+                    }
+                } else if probe.syncing {
+                    EndpointHealth::Syncing
+                } else if best_height - probe.height > options.max_block_lag {
+                    EndpointHealth::Behind {
+                        lag: best_height - probe.height,
+                    }
+                } else {
+                    EndpointHealth::Healthy
+                };
+            }
+            Err(err) => {
+                status.health = EndpointHealth::Unreachable {
+                    error: err.to_string(),
+                }
+            }
+        }
+    }
+}
+
+/// Spawn the periodic health check. The task stops once every clone of the channel has been dropped.
+pub(crate) fn spawn_health_check(
+    channel: &GrpcChannel,
+    chain_id: String,
+    options: HealthCheckOptions,
+) {
+    let weak: Weak<Shared> = Arc::downgrade(&channel.shared);
+
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(options.interval).await;
+
+            let Some(shared) = weak.upgrade() else {
+                break;
+            };
+
+            check_health(
+                &GrpcChannel {
+                    shared,
+                    height: None,
+                    node: None,
+                },
+                &chain_id,
+                options,
+            )
+            .await;
+        }
+    });
+}
+
+#[cfg(test)]
+#[allow(clippy::result_large_err)]
+mod test {
+    use {
+        super::{
+            check_health, EndpointHealth, GrpcChannel, HealthCheckOptions, BLOCK_HEIGHT_METADATA,
+        },
+        crate::mock::{latest_block, mock_channel, mock_node, MOCK_CHAIN_ID},
+        cosmos_sdk_proto::cosmos::base::tendermint::v1beta1::{
+            service_client::ServiceClient as TendermintClient, GetLatestBlockRequest,
+            GetSyncingResponse,
+        },
+        prost::Message,
+    };
+
+    async fn latest_height(channel: GrpcChannel) -> i64 {
+        TendermintClient::new(channel)
+            .get_latest_block(GetLatestBlockRequest {})
+            .await
+            .unwrap()
+            .into_inner()
+            .sdk_block
+            .unwrap()
+            .header
+            .unwrap()
+            .height
+    }
+
+    #[tokio::test]
+    async fn dispatch() {
+        // The node returns the requested height, 100 if not set
+        let address = mock_node(|path, metadata, _| {
+            assert_eq!(
+                path,
+                "/cosmos.base.tendermint.v1beta1.Service/GetLatestBlock"
+            );
+
+            let height = metadata
+                .get(BLOCK_HEIGHT_METADATA)
+                .map(|height| height.to_str().unwrap().parse().unwrap())
+                .unwrap_or(100);
+
+            Ok(latest_block(height))
+        });
+
+        let channel = GrpcChannel::single(&address, mock_channel(&address));
+
+        let heights =
+            futures::future::join_all((0..50).map(|_| latest_height(channel.clone()))).await;
+        assert!(heights.iter().all(|height| *height == 100));
+        assert_eq!(latest_height(channel.at_height(42)).await, 42);
+
+        let channel = GrpcChannel::multi(vec![
+            (
+                "http://127.0.0.1:1".to_string(),
+                mock_channel("http://127.0.0.1:1"),
+            ),
+            (address.clone(), mock_channel(&address)),
+        ]);
+
+        let heights =
+            futures::future::join_all((0..50).map(|_| latest_height(channel.at_height(42)))).await;
+        assert!(heights.iter().all(|height| *height == 42));
+        assert!(matches!(
+            channel.endpoints_status()[0].health,
+            EndpointHealth::Unreachable { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn routing_at_height() {
+        // Each node returns its own height, to check which one served the request
+        let node = |height: i64| {
+            let address = mock_node(move |path, _, _| match path {
+                "/cosmos.base.tendermint.v1beta1.Service/GetSyncing" => {
+                    Ok(GetSyncingResponse { syncing: false }.encode_to_vec())
+                }
+                _ => Ok(latest_block(height)),
+            });
+
+            (address.clone(), mock_channel(&address))
+        };
+
+        let channel = GrpcChannel::multi(vec![node(50), node(100)]);
+
+        check_health(
+            &channel,
+            MOCK_CHAIN_ID,
+            HealthCheckOptions {
+                max_block_lag: 100,
+                ..Default::default()
+            },
+        )
+        .await;
+
+        assert_eq!(latest_height(channel.clone()).await, 50);
+        // The first node is behind the requested height
+        assert_eq!(latest_height(channel.at_height(80)).await, 100);
+        assert_eq!(latest_height(channel.at_height(40)).await, 50);
+
+        // A pinned node is used also if behind
+        let pinned = channel.pin_node();
+        assert_eq!(latest_height(pinned.clone()).await, 50);
+        assert_eq!(latest_height(pinned.at_height(80)).await, 50);
+    }
+}
@@ -1,5 +1,13 @@
 use {
-    crate::AnyResult,
+    crate::{
+        builder::{GrpcClientBuilder, MessageLimits},
+        channel::{latest_block_header, EndpointStatus, GrpcChannel},
+        errors::Error,
+        modules::auth::AccountDecoder,
+        pagination::{query_stream, PaginatedRequest, PaginatedResponse, PaginationOptions},
+        traits::{BlockHeight, QueryPath},
+        AnyResult,
+    },
     anyhow::anyhow,
     cosmos_sdk_proto::{
         cosmos::{
@@ -7,19 +15,22 @@ use {
             authz::v1beta1::query_client::QueryClient as AuthzClient,
             bank::v1beta1::query_client::QueryClient as BankClient,
             base::{
-                query::v1beta1::PageRequest,
                 reflection::{
                     v1beta1::reflection_service_client::ReflectionServiceClient as ReflectionClientV1,
                     v2alpha1::reflection_service_client::ReflectionServiceClient as ReflectionClientV2,
                 },
                 tendermint::v1beta1::{
-                    service_client::ServiceClient as TendermintClient, GetNodeInfoRequest,
+                    service_client::ServiceClient as TendermintClient, GetLatestBlockRequest,
+                    GetNodeInfoRequest,
                 },
             },
             distribution::v1beta1::query_client::QueryClient as DistributionClient,
             evidence::v1beta1::query_client::QueryClient as EvidenceClient,
             feegrant::v1beta1::query_client::QueryClient as FeeGrantClient,
-            gov::v1beta1::query_client::QueryClient as GovClient,
+            gov::{
+                v1::query_client::QueryClient as GovClientV1,
+                v1beta1::query_client::QueryClient as GovClient,
+            },
             mint::v1beta1::query_client::QueryClient as MintClient,
             params::v1beta1::query_client::QueryClient as ParamsClient,
             slashing::v1beta1::query_client::QueryClient as SlashingClient,
@@ -29,63 +40,107 @@ use {
         },
         cosmwasm::wasm::v1::{
             query_client::QueryClient as WasmClient, QueryContractsByCodeRequest,
-            QueryRawContractStateRequest, QuerySmartContractStateRequest,
+            QueryContractsByCodeResponse, QueryRawContractStateRequest,
+            QuerySmartContractStateRequest,
         },
     },
+    futures::{Stream, TryStreamExt},
     prost::Message,
     serde::{de::DeserializeOwned, Serialize},
-    tonic::transport::Channel,
+    std::future::Future,
 };
 
 #[derive(Clone)]
 pub struct StandardClients {
-    pub auth: AuthClient<Channel>,
-    pub authz: AuthzClient<Channel>,
-    pub bank: BankClient<Channel>,
-    pub distribution: DistributionClient<Channel>,
-    pub evidence: EvidenceClient<Channel>,
-    pub fee_grant: FeeGrantClient<Channel>,
-    pub gov: GovClient<Channel>,
-    pub mint: MintClient<Channel>,
-    pub params: ParamsClient<Channel>,
-    pub reflection_v1: ReflectionClientV1<Channel>,
-    pub reflection_v2: ReflectionClientV2<Channel>,
-    pub slashing: SlashingClient<Channel>,
-    pub staking: StakingClient<Channel>,
-    pub tendermint: TendermintClient<Channel>,
-    pub upgrade: UpgradeClient<Channel>,
-    pub wasm: WasmClient<Channel>,
-    pub tx: TxClient<Channel>,
+    pub auth: AuthClient<GrpcChannel>,
+    pub authz: AuthzClient<GrpcChannel>,
+    pub bank: BankClient<GrpcChannel>,
+    pub distribution: DistributionClient<GrpcChannel>,
+    pub evidence: EvidenceClient<GrpcChannel>,
+    pub fee_grant: FeeGrantClient<GrpcChannel>,
+    pub gov: GovClient<GrpcChannel>,
+    pub gov_v1: GovClientV1<GrpcChannel>,
+    pub mint: MintClient<GrpcChannel>,
+    pub params: ParamsClient<GrpcChannel>,
+    pub reflection_v1: ReflectionClientV1<GrpcChannel>,
+    pub reflection_v2: ReflectionClientV2<GrpcChannel>,
+    pub slashing: SlashingClient<GrpcChannel>,
+    pub staking: StakingClient<GrpcChannel>,
+    pub tendermint: TendermintClient<GrpcChannel>,
+    pub upgrade: UpgradeClient<GrpcChannel>,
+    pub wasm: WasmClient<GrpcChannel>,
+    pub tx: TxClient<GrpcChannel>,
 }
 
 #[non_exhaustive]
 #[derive(Clone)]
 pub struct GrpcClient {
-    inner: tonic::client::Grpc<Channel>,
+    channel: GrpcChannel,
+    limits: MessageLimits,
+    inner: tonic::client::Grpc<GrpcChannel>,
     pub chain_id: String,
     /// Standard cosmos_sdk query clients definition
     pub clients: StandardClients,
+    /// Decoder of the accounts returned by the `auth` module, used also by [`Wallet`](crate::Wallet).
+    pub account_decoder: AccountDecoder,
+}
+
+macro_rules! std_client {
+    ($client:ident, $channel:expr, $limits:expr) => {
+        $client::new($channel)
+            .max_decoding_message_size($limits.max_decoding_message_size)
+            .max_encoding_message_size($limits.max_encoding_message_size)
+    };
+}
+
+impl StandardClients {
+    pub(crate) fn new(channel: GrpcChannel, limits: MessageLimits) -> StandardClients {
+        StandardClients {
+            auth: std_client!(AuthClient, channel.clone(), limits),
+            authz: std_client!(AuthzClient, channel.clone(), limits),
+            bank: std_client!(BankClient, channel.clone(), limits),
+            distribution: std_client!(DistributionClient, channel.clone(), limits),
+            evidence: std_client!(EvidenceClient, channel.clone(), limits),
+            fee_grant: std_client!(FeeGrantClient, channel.clone(), limits),
+            gov: std_client!(GovClient, channel.clone(), limits),
+            gov_v1: std_client!(GovClientV1, channel.clone(), limits),
+            mint: std_client!(MintClient, channel.clone(), limits),
+            params: std_client!(ParamsClient, channel.clone(), limits),
+            reflection_v1: std_client!(ReflectionClientV1, channel.clone(), limits),
+            reflection_v2: std_client!(ReflectionClientV2, channel.clone(), limits),
+            slashing: std_client!(SlashingClient, channel.clone(), limits),
+            staking: std_client!(StakingClient, channel.clone(), limits),
+            tendermint: std_client!(TendermintClient, channel.clone(), limits),
+            upgrade: std_client!(UpgradeClient, channel.clone(), limits),
+            wasm: std_client!(WasmClient, channel.clone(), limits),
+            tx: std_client!(TxClient, channel, limits),
+        }
+    }
 }
 
 impl GrpcClient {
     pub async fn new(gprc_addres: impl Into<String>) -> AnyResult<GrpcClient> {
-        let channel = Channel::builder(Into::<String>::into(gprc_addres).parse()?)
-            .connect()
-            .await?;
-
-        Self::build(channel).await
+        GrpcClientBuilder::new(gprc_addres).build().await
     }
 
     pub async fn new_from_static(grpc_address: &'static str) -> AnyResult<GrpcClient> {
-        let channel = Channel::from_static(grpc_address).connect().await?;
+        GrpcClientBuilder::new(grpc_address).build().await
+    }
 
-        Self::build(channel).await
+    /// Create a [`GrpcClientBuilder`] to customize timeouts, keepalive, TLS and message size limits.
+    pub fn builder(grpc_address: impl Into<String>) -> GrpcClientBuilder {
+        GrpcClientBuilder::new(grpc_address)
     }
 
-    async fn build(channel: Channel) -> AnyResult<GrpcClient> {
-        let mut tendermint_client = TendermintClient::new(channel.clone());
+    pub(crate) async fn build(
+        channel: GrpcChannel,
+        limits: MessageLimits,
+    ) -> AnyResult<GrpcClient> {
+        let clients = StandardClients::new(channel.clone(), limits);
 
-        let chain_id = tendermint_client
+        let chain_id = clients
+            .tendermint
+            .clone()
             .get_node_info(GetNodeInfoRequest {})
             .await?
             .into_inner()
@@ -93,31 +148,136 @@ impl GrpcClient {
             .ok_or(anyhow!("No node info"))?
             .network;
 
-        Ok(GrpcClient {
-            inner: tonic::client::Grpc::new(channel.clone()),
+        Ok(GrpcClient::from_parts(
+            channel,
+            limits,
             chain_id,
-            clients: StandardClients {
-                auth: AuthClient::new(channel.clone()),
-                authz: AuthzClient::new(channel.clone()),
-                bank: BankClient::new(channel.clone()),
-                distribution: DistributionClient::new(channel.clone()),
-                evidence: EvidenceClient::new(channel.clone()),
-                fee_grant: FeeGrantClient::new(channel.clone()),
-                gov: GovClient::new(channel.clone()),
-                mint: MintClient::new(channel.clone()),
-                params: ParamsClient::new(channel.clone()),
-                reflection_v1: ReflectionClientV1::new(channel.clone()),
-                reflection_v2: ReflectionClientV2::new(channel.clone()),
-                slashing: SlashingClient::new(channel.clone()),
-                staking: StakingClient::new(channel.clone()),
-                tendermint: tendermint_client,
-                upgrade: UpgradeClient::new(channel.clone()),
-                wasm: WasmClient::new(channel.clone()),
-                tx: TxClient::new(channel),
-            },
+            clients,
+            AccountDecoder::default(),
+        ))
+    }
+
+    fn from_parts(
+        channel: GrpcChannel,
+        limits: MessageLimits,
+        chain_id: String,
+        clients: StandardClients,
+        account_decoder: AccountDecoder,
+    ) -> GrpcClient {
+        GrpcClient {
+            channel: channel.clone(),
+            limits,
+            inner: tonic::client::Grpc::new(channel)
+                .max_decoding_message_size(limits.max_decoding_message_size)
+                .max_encoding_message_size(limits.max_encoding_message_size),
+            chain_id,
+            clients,
+            account_decoder,
+        }
+    }
+
+    /// Return a view of the client where every query (`clients`, `proto_query`, wasm helpers)
+    /// is performed against the state at `height`, by attaching the `x-cosmos-block-height` metadata.
+    ///
+    /// Querying a height older than the pruning window of the node returns an error,
+    /// use an archive node to query historical data.
+    /// ## Example:
+    /// ``` ignore
+    /// let client = GrpcClient::new(OSMOSIS_GRPC_MAINNET).await.unwrap();
+    ///
+    /// let old_state: Response = client
+    ///     .at_height(12_000_000)
+    ///     .query_smart_contract("osmo1...", QueryMsg::State {})
+    ///     .await
+    ///     .unwrap();
+    /// ```
+    pub fn at_height(&self, height: u64) -> GrpcClient {
+        self.with_channel(self.channel.at_height(height))
+    }
+
+    fn with_channel(&self, channel: GrpcChannel) -> GrpcClient {
+        GrpcClient::from_parts(
+            channel.clone(),
+            self.limits,
+            self.chain_id.clone(),
+            StandardClients::new(channel, self.limits),
+            self.account_decoder.clone(),
+        )
+    }
+
+    /// Height of the latest block of the node.
+    pub async fn latest_height(&self) -> AnyResult<u64> {
+        let res = self
+            .clients
+            .tendermint
+            .clone()
+            .get_latest_block(GetLatestBlockRequest {})
+            .await?
+            .into_inner();
+
+        Ok(latest_block_header(res)?.0)
+    }
+
+    /// Run a set of queries against a consistent state.
+    ///
+    /// The latest height is resolved once and `queries` receives a view of the client pinned to it
+    /// (see [`GrpcClient::at_height`]), so every query performed inside the closure reads the same block.
+    /// With more than one endpoint, the height is resolved and the queries are performed on the same node.
+    /// Returns an error if the node has pruned the state at that height.
+    /// ## Example:
+    /// ``` ignore
+    /// let (state, balance) = client
+    ///     .snapshot(|view| async move {
+    ///         let state: StateResponse = view
+    ///             .query_smart_contract("osmo1...", QueryMsg::State {})
+    ///             .await?;
+    ///
+    ///         let balance = view
+    ///             .clients
+    ///             .bank
+    ///             .clone()
+    ///             .balance(QueryBalanceRequest {
+    ///                 address: "osmo1...".to_string(),
+    ///                 denom: "uosmo".to_string(),
+    ///             })
+    ///             .await?
+    ///             .into_inner();
+    ///
+    ///         Ok((state, balance))
+    ///     })
+    ///     .await
+    ///     .unwrap();
+    /// ```
+    pub async fn snapshot<F, Fut, T>(&self, queries: F) -> AnyResult<T>
+    where
+        F: FnOnce(GrpcClient) -> Fut,
+        Fut: Future<Output = AnyResult<T>>,
+    {
+        // A different node could be behind the resolved height
+        let client = self.with_channel(self.channel.pin_node());
+        let height = client.latest_height().await?;
+
+        queries(client.at_height(height)).await.map_err(|err| {
+            if is_pruned_height_error(&err) {
+                err.context(format!(
+                    "State at height {height} has been pruned by the node"
+                ))
+            } else {
+                err
+            }
         })
     }
 
+    /// Height the client is pinned to. `None` if queries are performed on latest state.
+    pub fn height(&self) -> Option<u64> {
+        self.channel.height()
+    }
+
+    /// Status of the endpoints used by the client, as reported by the last health check.
+    pub fn endpoints_status(&self) -> Vec<EndpointStatus> {
+        self.channel.endpoints_status()
+    }
+
     /// Perform a query from a any module (also custom module), where:
     /// - `Q`: Query request serializabile into `prost::Message`
     /// - `R`: Query request deserializable into `prost::Message`
@@ -141,25 +301,76 @@ impl GrpcClient {
     ///     let pool = Pool::decode(response.pool.unwrap().value.as_slice()).unwrap();
     /// }
     /// ```
-
     pub async fn proto_query<Q, R>(&self, request: Q, type_url: impl Into<String>) -> AnyResult<R>
+    where
+        Q: Send + Sync + Message + tonic::IntoRequest<Q> + 'static,
+        R: Send + Sync + Message + Default + 'static,
+    {
+        Ok(self
+            .proto_query_response(request, type_url)
+            .await?
+            .into_inner())
+    }
+
+    /// Same as [`GrpcClient::proto_query`], with the path of the query given by the [`QueryPath`] of the request.
+    /// ## Example:
+    /// ``` ignore
+    /// use cosmos_sdk_proto::cosmos::staking::v1beta1::{QueryValidatorRequest, QueryValidatorResponse};
+    ///
+    /// // Path: "/cosmos.staking.v1beta1.Query/Validator"
+    /// let response: QueryValidatorResponse = client
+    ///     .proto_query_named(QueryValidatorRequest {
+    ///         validator_addr: "osmovaloper1...".to_string(),
+    ///     })
+    ///     .await
+    ///     .unwrap();
+    /// ```
+    pub async fn proto_query_named<Q, R>(&self, request: Q) -> AnyResult<R>
+    where
+        Q: Send + Sync + Message + QueryPath + tonic::IntoRequest<Q> + 'static,
+        R: Send + Sync + Message + Default + 'static,
+    {
+        self.proto_query(request, Q::query_path()).await
+    }
+
+    /// Same as [`GrpcClient::proto_query`], returning also the height of the state the response has been read from.
+    pub async fn proto_query_with_height<Q, R>(
+        &self,
+        request: Q,
+        type_url: impl Into<String>,
+    ) -> AnyResult<(R, Option<u64>)>
+    where
+        Q: Send + Sync + Message + tonic::IntoRequest<Q> + 'static,
+        R: Send + Sync + Message + Default + 'static,
+    {
+        let response = self.proto_query_response(request, type_url).await?;
+        let height = response.block_height();
+
+        Ok((response.into_inner(), height))
+    }
+
+    async fn proto_query_response<Q, R>(
+        &self,
+        request: Q,
+        type_url: impl Into<String>,
+    ) -> AnyResult<tonic::Response<R>>
     where
         Q: Send + Sync + Message + tonic::IntoRequest<Q> + 'static,
         R: Send + Sync + Message + Default + 'static,
     {
         let mut client = self.inner.clone();
 
-        client.ready().await?;
+        client
+            .ready()
+            .await
+            .map_err(|err| Error::Transport(err.to_string()))?;
 
         let codec: tonic::codec::ProstCodec<Q, R> = tonic::codec::ProstCodec::default();
-        let path = tonic::codegen::http::uri::PathAndQuery::from_static(Box::leak(
-            type_url.into().into_boxed_str(),
-        ));
+        let path = tonic::codegen::http::uri::PathAndQuery::try_from(type_url.into())?;
 
         Ok(client
             .unary::<Q, R, tonic::codec::ProstCodec<Q, R>>(request.into_request(), path, codec)
-            .await?
-            .into_inner())
+            .await?)
     }
 
     pub async fn query_smart_contract<Request: Serialize, Response: DeserializeOwned>(
@@ -222,41 +433,85 @@ impl GrpcClient {
     }
 
     pub async fn wasm_get_contracts_from_code_id(&self, code_id: u64) -> AnyResult<Vec<String>> {
-        let mut pagination = None;
-        let mut contracts: Vec<String> = vec![];
-        let mut finish = false;
-
-        let mut wasm = self.clients.wasm.clone();
-        while !finish {
-            let mut res = wasm
-                .contracts_by_code(QueryContractsByCodeRequest {
-                    code_id,
-                    pagination: pagination.clone(),
-                })
-                .await?
-                .into_inner();
-
-            contracts.append(&mut res.contracts);
-
-            match res.pagination {
-                Some(val) => {
-                    if !val.next_key.is_empty() {
-                        pagination = Some(PageRequest {
-                            key: val.next_key,
-                            ..Default::default()
-                        })
-                    } else {
-                        finish = true
-                    }
-                }
-                None => finish = true,
-            }
-        }
+        self.proto_query_all::<_, QueryContractsByCodeResponse>(
+            QueryContractsByCodeRequest {
+                code_id,
+                pagination: None,
+            },
+            "/cosmwasm.wasm.v1.Query/ContractsByCode",
+            PaginationOptions::default(),
+        )
+        .await
+    }
 
-        Ok(contracts)
+    /// Perform a paginated [`GrpcClient::proto_query`], collecting the items of all the pages.
+    /// ## Example:
+    /// ``` ignore
+    /// let balances: Vec<Coin> = client
+    ///     .proto_query_all(
+    ///         QueryAllBalancesRequest {
+    ///             address: "osmo1...".to_string(),
+    ///             pagination: None,
+    ///         },
+    ///         "/cosmos.bank.v1beta1.Query/AllBalances",
+    ///         PaginationOptions::default().page_size(100),
+    ///     )
+    ///     .await
+    ///     .unwrap();
+    /// ```
+    pub async fn proto_query_all<Q, R>(
+        &self,
+        request: Q,
+        type_url: impl Into<String>,
+        options: PaginationOptions,
+    ) -> AnyResult<Vec<R::Item>>
+    where
+        Q: Send + Sync + Message + PaginatedRequest + tonic::IntoRequest<Q> + 'static,
+        R: Send + Sync + Message + Default + PaginatedResponse + 'static,
+    {
+        self.proto_query_stream::<Q, R>(request, type_url, options)
+            .try_collect()
+            .await
+    }
+
+    /// Perform a paginated [`GrpcClient::proto_query`], streaming the items.
+    /// Pages are requested only when the items of the previous one have been consumed.
+    pub fn proto_query_stream<Q, R>(
+        &self,
+        request: Q,
+        type_url: impl Into<String>,
+        options: PaginationOptions,
+    ) -> impl Stream<Item = AnyResult<R::Item>> + '_
+    where
+        Q: Send + Sync + Message + PaginatedRequest + tonic::IntoRequest<Q> + 'static,
+        R: Send + Sync + Message + Default + PaginatedResponse + 'static,
+    {
+        let type_url: String = type_url.into();
+
+        query_stream(request, options, move |request| {
+            self.proto_query::<Q, R>(request, type_url.clone())
+        })
     }
 }
 
+/// Check if the error has been returned by a node that doesn't hold the state at the requested height.
+fn is_pruned_height_error(err: &anyhow::Error) -> bool {
+    const PRUNED_MESSAGES: [&str; 4] = [
+        "version does not exist",
+        "failed to load state at height",
+        "is not available, lowest height is",
+        "pruned",
+    ];
+
+    err.chain()
+        .filter_map(|err| err.downcast_ref::<tonic::Status>())
+        .any(|status| {
+            PRUNED_MESSAGES
+                .iter()
+                .any(|msg| status.message().contains(msg))
+        })
+}
+
 #[cfg(test)]
 #[cfg(feature = "osmosis")]
 #[allow(dead_code)]
@@ -1,10 +1,14 @@
 use enum_repr::EnumRepr;
 pub const LOCAL_NODE_GPRC: &str = "http://localhost:9090";
 
+#[cfg_attr(not(feature = "osmosis"), allow(dead_code))]
 pub const OSMOSIS_GRPC_MAINNET: &str = "https://osmosis-grpc.polkachu.com:12590";
+#[cfg_attr(not(feature = "osmosis"), allow(dead_code))]
 pub const OSMOSIS_GRPC_TESTNET: &str = "https://osmosis-testnet-grpc.polkachu.com:12590";
 
+#[cfg_attr(not(feature = "injective"), allow(dead_code))]
 pub const INJECTIVE_GRPC_MAINNET: &str = "https://injective-grpc.polkachu.com:14390";
+#[cfg_attr(not(feature = "injective"), allow(dead_code))]
 pub const INJECTIVE_GRPC_TESTNET: &str = "https://injective-testnet-grpc.polkachu.com:14390";
 
 #[EnumRepr(type = "i32")]
@@ -14,12 +18,16 @@ pub enum BroadcastMode {
     Async = 3,
 }
 
-#[derive(Clone)]
+/// Well known BIP-44 coin types. Any other coin type can be used as `u64`, see [`KeyConfig`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[EnumRepr(type = "u64")]
 pub enum CoinType {
     Injective = 60,
     Cosmos = 118,
     Terra = 330,
+    CryptoOrg = 394,
+    Kava = 459,
+    Secret = 529,
 }
 
 impl From<CoinType> for u64 {
@@ -27,3 +35,215 @@ impl From<CoinType> for u64 {
         val as u64
     }
 }
+
+/// Signature scheme of the key of a [`Wallet`](crate::Wallet).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KeyAlgorithm {
+    /// `cosmos.crypto.secp256k1`, address derived with `ripemd160(sha256(pubkey))`.
+    Secp256k1,
+    /// `eth_secp256k1` of ethermint based chains, address derived with `keccak256(pubkey)`
+    /// and sign doc signed with `keccak256`.
+    EthSecp256k1(EthKeyConfig),
+}
+
+/// Type urls used by a chain for the `eth_secp256k1` keys.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EthKeyConfig {
+    /// Type url of the public key in the `AuthInfo` of the tx.
+    pub pubkey_type_url: String,
+    /// Type url of the accounts returned by the `auth` module.
+    pub account_type_url: String,
+    /// Amino name of the private key, used by the [`Keystore`](crate::Keystore) armor.
+    pub private_key_amino_name: String,
+}
+
+impl EthKeyConfig {
+    pub fn new(
+        pubkey_type_url: impl Into<String>,
+        account_type_url: impl Into<String>,
+        private_key_amino_name: impl Into<String>,
+    ) -> EthKeyConfig {
+        EthKeyConfig {
+            pubkey_type_url: pubkey_type_url.into(),
+            account_type_url: account_type_url.into(),
+            private_key_amino_name: private_key_amino_name.into(),
+        }
+    }
+
+    pub fn injective() -> EthKeyConfig {
+        EthKeyConfig::new(
+            "/injective.crypto.v1beta1.ethsecp256k1.PubKey",
+            "/injective.types.v1beta1.EthAccount",
+            "injective/PrivKeyEthSecp256k1",
+        )
+    }
+
+    /// Used by Evmos, Cronos, Kava EVM, Dymension and the other chains based on ethermint.
+    pub fn ethermint() -> EthKeyConfig {
+        EthKeyConfig::new(
+            "/ethermint.crypto.v1.ethsecp256k1.PubKey",
+            "/ethermint.types.v1.EthAccount",
+            "ethermint/PrivKeyEthSecp256k1",
+        )
+    }
+}
+
+/// Derivation path and signature scheme of the key of a [`Wallet`](crate::Wallet).
+///
+/// A `u64` or a [`CoinType`] always converts to [`KeyAlgorithm::Secp256k1`], including `60`.
+/// Use [`KeyConfig::injective`] or [`KeyConfig::ethermint`] for the `eth_secp256k1` keys.
+/// ## Example:
+/// ``` ignore
+/// // Secret
+/// Wallet::from_seed_phrase(client, seed_phrase, "secret", 529, 0, gas_price, gas_adjustment, "uscrt").await?;
+///
+/// // Injective
+/// Wallet::from_seed_phrase(client, seed_phrase, "inj", KeyConfig::injective(), 0, gas_price, gas_adjustment, "inj").await?;
+///
+/// // Evmos
+/// Wallet::from_seed_phrase(client, seed_phrase, "evmos", KeyConfig::ethermint(), 0, gas_price, gas_adjustment, "aevmos").await?;
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyConfig {
+    /// BIP-44 coin type, used in the derivation path `m/44'/{coin_type}'/0'/0/{index}`.
+    pub coin_type: u64,
+    pub algorithm: KeyAlgorithm,
+}
+
+impl KeyConfig {
+    pub fn new(coin_type: impl Into<u64>, algorithm: KeyAlgorithm) -> KeyConfig {
+        KeyConfig {
+            coin_type: coin_type.into(),
+            algorithm,
+        }
+    }
+
+    /// Coin type 60 with the Injective `eth_secp256k1` keys.
+    pub fn injective() -> KeyConfig {
+        KeyConfig::new(
+            CoinType::Injective,
+            KeyAlgorithm::EthSecp256k1(EthKeyConfig::injective()),
+        )
+    }
+
+    /// Coin type 60 with the ethermint `eth_secp256k1` keys.
+    pub fn ethermint() -> KeyConfig {
+        KeyConfig::new(
+            60_u64,
+            KeyAlgorithm::EthSecp256k1(EthKeyConfig::ethermint()),
+        )
+    }
+}
+
+impl From<u64> for KeyConfig {
+    fn from(coin_type: u64) -> Self {
+        KeyConfig::new(coin_type, KeyAlgorithm::Secp256k1)
+    }
+}
+
+impl From<CoinType> for KeyConfig {
+    fn from(coin_type: CoinType) -> Self {
+        KeyConfig::from(coin_type as u64)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::result_large_err)]
+mod test {
+    use {
+        crate::{
+            mock::{mock_client, mock_fee},
+            CoinType, EthKeyConfig, GrpcClient, KeyAlgorithm, KeyConfig, TxOptions, Wallet,
+        },
+        cosmos_sdk_proto::cosmos::tx::v1beta1::{AuthInfo, TxRaw},
+        cosmrs::AccountId,
+        cosmwasm_std::Decimal,
+        prost::Message,
+        prost_types::Any,
+        tonic::Status,
+    };
+
+    #[test]
+    fn key_config_conversions() {
+        let secp256k1 = |coin_type: u64| KeyConfig {
+            coin_type,
+            algorithm: KeyAlgorithm::Secp256k1,
+        };
+
+        assert_eq!(KeyConfig::from(60), secp256k1(60));
+        assert_eq!(KeyConfig::from(CoinType::Injective), secp256k1(60));
+        assert_eq!(KeyConfig::from(CoinType::Terra), secp256k1(330));
+        assert_eq!(KeyConfig::from(1234), secp256k1(1234));
+
+        assert_eq!(
+            KeyConfig::injective(),
+            KeyConfig {
+                coin_type: 60,
+                algorithm: KeyAlgorithm::EthSecp256k1(EthKeyConfig {
+                    pubkey_type_url: "/injective.crypto.v1beta1.ethsecp256k1.PubKey".to_string(),
+                    account_type_url: "/injective.types.v1beta1.EthAccount".to_string(),
+                    private_key_amino_name: "injective/PrivKeyEthSecp256k1".to_string(),
+                }),
+            }
+        );
+        assert_eq!(
+            KeyConfig::ethermint(),
+            KeyConfig {
+                coin_type: 60,
+                algorithm: KeyAlgorithm::EthSecp256k1(EthKeyConfig {
+                    pubkey_type_url: "/ethermint.crypto.v1.ethsecp256k1.PubKey".to_string(),
+                    account_type_url: "/ethermint.types.v1.EthAccount".to_string(),
+                    private_key_amino_name: "ethermint/PrivKeyEthSecp256k1".to_string(),
+                }),
+            }
+        );
+    }
+
+    async fn wallet(client: &GrpcClient, key_config: KeyConfig, prefix: &str) -> Wallet {
+        Wallet::from_private_key(
+            client.clone(),
+            // Private key of the web3.js accounts documentation
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+            prefix,
+            key_config,
+            Decimal::percent(1),
+            Decimal::percent(150),
+            "inj",
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn eth_address() {
+        let client = mock_client(|_, _, _| Err(Status::not_found("account not found"))).await;
+        let address_bytes =
+            subtle_encoding::hex::decode("2c7536e3605d9c16a7a3d7b1898e529396a65c23").unwrap();
+        let address = |prefix: &str| AccountId::new(prefix, &address_bytes).unwrap().to_string();
+
+        let injective = wallet(&client, KeyConfig::injective(), "inj").await;
+        assert_eq!(injective.account_address, address("inj"));
+
+        let evmos = wallet(&client, KeyConfig::ethermint(), "evmos").await;
+        assert_eq!(evmos.account_address, address("evmos"));
+
+        let secp256k1 = wallet(&client, KeyConfig::from(60), "inj").await;
+        assert_ne!(secp256k1.account_address, address("inj"));
+
+        // The eth keys are signed with the public key type url of the chain
+        let raw = injective
+            .create_tx(vec![Any::default()], mock_fee(), None, TxOptions::default())
+            .unwrap();
+        let tx = TxRaw::decode(raw.to_bytes().unwrap().as_slice()).unwrap();
+        let auth_info = AuthInfo::decode(tx.auth_info_bytes.as_slice()).unwrap();
+        assert_eq!(
+            auth_info.signer_infos[0]
+                .public_key
+                .as_ref()
+                .unwrap()
+                .type_url,
+            "/injective.crypto.v1beta1.ethsecp256k1.PubKey"
+        );
+        assert_eq!(tx.signatures[0].len(), 64);
+    }
+}
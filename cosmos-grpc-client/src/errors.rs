@@ -1 +1,460 @@
+use {
+    crate::sequence::{is_sequence_mismatch, SEQUENCE_MISMATCH_CODE},
+    cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxResponse,
+    std::{error::Error as StdError, fmt::Display},
+    tonic::Code,
+};
 
+/// Codespace of the errors defined by cosmos-sdk.
+pub const SDK_CODESPACE: &str = "sdk";
+
+/// Typed error carried by the [`anyhow::Error`] returned by [`GrpcClient`](crate::GrpcClient)
+/// and [`Wallet`](crate::Wallet) methods. Use [`ErrorExt`] to extract it.
+#[derive(Clone, Debug)]
+pub enum Error {
+    /// The node is unreachable or the connection has been dropped.
+    Transport(String),
+    /// The node returned a gRPC error status.
+    Status(tonic::Status),
+    /// The response can't be decoded.
+    Decode(prost::DecodeError),
+    /// The tx has been rejected by the chain, in the simulation, `CheckTx` or `DeliverTx`.
+    ///
+    /// Also returned for the gRPC statuses of `Simulate` and `BroadcastTx` reporting an ante handler failure.
+    ChainRejection {
+        /// Empty if the tx has been rejected in the simulation.
+        txhash: String,
+        codespace: String,
+        code: u32,
+        raw_log: String,
+        kind: RejectionKind,
+    },
+}
+
+/// Reason of a [`Error::ChainRejection`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RejectionKind {
+    SequenceMismatch,
+    InsufficientFee,
+    OutOfGas,
+    Unauthorized,
+    NotFound,
+    Other,
+}
+
+impl RejectionKind {
+    /// Classify an ABCI `code` of `codespace`.
+    pub fn from_code(codespace: &str, code: u32) -> RejectionKind {
+        if codespace != SDK_CODESPACE {
+            return RejectionKind::Other;
+        }
+
+        match code {
+            // ErrInvalidSequence and ErrWrongSequence
+            3 | SEQUENCE_MISMATCH_CODE => RejectionKind::SequenceMismatch,
+            13 => RejectionKind::InsufficientFee,
+            11 => RejectionKind::OutOfGas,
+            4 => RejectionKind::Unauthorized,
+            // ErrKeyNotFound and ErrNotFound
+            22 | 38 => RejectionKind::NotFound,
+            _ => RejectionKind::Other,
+        }
+    }
+
+    /// Classify the log of a tx rejected in the simulation, where the ABCI code is not returned.
+    fn from_log(log: &str) -> Option<(RejectionKind, u32)> {
+        if is_sequence_mismatch(log) {
+            Some((RejectionKind::SequenceMismatch, SEQUENCE_MISMATCH_CODE))
+        } else if log.contains("insufficient fee") {
+            Some((RejectionKind::InsufficientFee, 13))
+        } else if log.contains("out of gas") {
+            Some((RejectionKind::OutOfGas, 11))
+        } else if log.contains("unauthorized") {
+            Some((RejectionKind::Unauthorized, 4))
+        } else {
+            None
+        }
+    }
+}
+
+impl Error {
+    /// Return the rejection of a tx, `None` if `code == 0`.
+    pub fn from_tx_response(response: &TxResponse) -> Option<Error> {
+        if response.code == 0 {
+            return None;
+        }
+
+        Some(Error::ChainRejection {
+            txhash: response.txhash.clone(),
+            codespace: response.codespace.clone(),
+            code: response.code,
+            raw_log: response.raw_log.clone(),
+            kind: RejectionKind::from_code(&response.codespace, response.code),
+        })
+    }
+
+    /// Classify the status returned by the `Simulate` and `BroadcastTx` endpoints,
+    /// converting the ante handler failures into [`Error::ChainRejection`].
+    pub(crate) fn from_tx_status(status: tonic::Status) -> Error {
+        match RejectionKind::from_log(status.message()) {
+            Some((kind, code)) if status.code() != Code::Unavailable => Error::ChainRejection {
+                txhash: String::new(),
+                codespace: SDK_CODESPACE.to_string(),
+                code,
+                raw_log: status.message().to_string(),
+                kind,
+            },
+            _ => status.into(),
+        }
+    }
+
+    pub fn rejection_kind(&self) -> Option<RejectionKind> {
+        match self {
+            Error::ChainRejection { kind, .. } => Some(*kind),
+            _ => None,
+        }
+    }
+
+    pub fn is_sequence_mismatch(&self) -> bool {
+        self.rejection_kind() == Some(RejectionKind::SequenceMismatch)
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            Error::Status(status) => status.code() == Code::NotFound,
+            _ => self.rejection_kind() == Some(RejectionKind::NotFound),
+        }
+    }
+
+    /// Check if sending the same request again can succeed.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::Transport(_) => true,
+            Error::Status(status) => matches!(
+                status.code(),
+                Code::Unavailable
+                    | Code::DeadlineExceeded
+                    | Code::ResourceExhausted
+                    | Code::Aborted
+            ),
+            Error::Decode(_) => false,
+            // ErrMempoolIsFull
+            Error::ChainRejection {
+                codespace, code, ..
+            } if codespace == SDK_CODESPACE && *code == 20 => true,
+            Error::ChainRejection { kind, .. } => *kind == RejectionKind::SequenceMismatch,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Transport(err) => write!(f, "Transport error: {err}"),
+            Error::Status(status) => {
+                write!(f, "Grpc status {:?}: {}", status.code(), status.message())
+            }
+            Error::Decode(err) => write!(f, "Decode error: {err}"),
+            Error::ChainRejection {
+                txhash,
+                codespace,
+                code,
+                raw_log,
+                ..
+            } => {
+                if txhash.is_empty() {
+                    write!(
+                        f,
+                        "Tx rejected with code {code} (codespace: {codespace}): {raw_log}"
+                    )
+                } else {
+                    write!(
+                        f,
+                        "Tx {txhash} failed with code {code} (codespace: {codespace}): {raw_log}"
+                    )
+                }
+            }
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Error::Status(status) => Some(status),
+            Error::Decode(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<tonic::Status> for Error {
+    fn from(status: tonic::Status) -> Self {
+        // Statuses with a source are created by tonic from the errors of the channel
+        if status.code() == Code::Unavailable
+            || (status.code() == Code::Unknown && status.source().is_some())
+        {
+            Error::Transport(status.message().to_string())
+        } else {
+            Error::Status(status)
+        }
+    }
+}
+
+impl From<tonic::transport::Error> for Error {
+    fn from(err: tonic::transport::Error) -> Self {
+        Error::Transport(format!("{err:#}"))
+    }
+}
+
+impl From<prost::DecodeError> for Error {
+    fn from(err: prost::DecodeError) -> Self {
+        Error::Decode(err)
+    }
+}
+
+/// Inspect the [`Error`] carried by an [`anyhow::Error`].
+///
+/// ## Example:
+/// ``` ignore
+/// match wallet.broadcast_tx(msgs, None, None, TxOptions::default(), BroadcastMode::Sync).await {
+///     Err(err) if err.is_retryable() => { /* try again later */ }
+///     res => { res?; }
+/// }
+/// ```
+pub trait ErrorExt {
+    /// First typed error found in the chain of the error.
+    fn grpc_error(&self) -> Option<Error>;
+
+    fn rejection_kind(&self) -> Option<RejectionKind> {
+        self.grpc_error().and_then(|err| err.rejection_kind())
+    }
+
+    fn is_sequence_mismatch(&self) -> bool {
+        self.grpc_error()
+            .is_some_and(|err| err.is_sequence_mismatch())
+    }
+
+    fn is_not_found(&self) -> bool {
+        self.grpc_error().is_some_and(|err| err.is_not_found())
+    }
+
+    fn is_retryable(&self) -> bool {
+        self.grpc_error().is_some_and(|err| err.is_retryable())
+    }
+}
+
+impl ErrorExt for anyhow::Error {
+    fn grpc_error(&self) -> Option<Error> {
+        self.chain().find_map(|err| {
+            if let Some(err) = err.downcast_ref::<Error>() {
+                Some(err.clone())
+            } else if let Some(status) = err.downcast_ref::<tonic::Status>() {
+                Some(status.clone().into())
+            } else if let Some(err) = err.downcast_ref::<tonic::transport::Error>() {
+                Some(Error::Transport(format!("{err:#}")))
+            } else {
+                err.downcast_ref::<prost::DecodeError>()
+                    .map(|err| Error::Decode(err.clone()))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::result_large_err)]
+mod test {
+    use {
+        super::{Error, ErrorExt, RejectionKind},
+        crate::{
+            check_tx_response, mock::mock_client, BroadcastMode, CoinType, ProstMsgNameToAny,
+            RetryPolicy, TxOptions, WaitOptions, Wallet,
+        },
+        anyhow::anyhow,
+        cosmos_sdk_proto::cosmos::{
+            bank::v1beta1::MsgSend,
+            base::{abci::v1beta1::TxResponse, v1beta1::Coin},
+            tx::v1beta1::{
+                BroadcastTxRequest, BroadcastTxResponse, SimulateRequest, TxBody, TxRaw,
+            },
+        },
+        cosmrs::tx::Fee,
+        cosmwasm_std::Decimal,
+        prost::Message,
+        tonic::Status,
+    };
+
+    fn memo(tx_bytes: &[u8]) -> String {
+        let tx = TxRaw::decode(tx_bytes).unwrap();
+        TxBody::decode(tx.body_bytes.as_slice()).unwrap().memo
+    }
+
+    #[test]
+    fn classification() {
+        let response = TxResponse {
+            txhash: "ABC".to_string(),
+            codespace: "sdk".to_string(),
+            code: 13,
+            raw_log: "insufficient fees; got: 1uosmo required: 10uosmo: insufficient fee"
+                .to_string(),
+            ..Default::default()
+        };
+
+        let err = Error::from_tx_response(&response).unwrap();
+        assert_eq!(err.rejection_kind(), Some(RejectionKind::InsufficientFee));
+        assert!(!err.is_retryable());
+        assert_eq!(RejectionKind::from_code("wasm", 13), RejectionKind::Other);
+
+        let err = Error::from_tx_status(tonic::Status::unknown(
+            "account sequence mismatch, expected 10, got 9: incorrect account sequence",
+        ));
+        assert!(err.is_sequence_mismatch());
+        assert!(anyhow::Error::from(err).context("broadcast").is_retryable());
+
+        let err = anyhow::Error::from(tonic::Status::not_found("account not found"));
+        assert!(err.is_not_found());
+        assert!(!err.is_retryable());
+
+        assert!(
+            anyhow::Error::from(tonic::Status::unavailable("connection refused")).is_retryable()
+        );
+        assert!(anyhow!("generic error").grpc_error().is_none());
+    }
+
+    #[tokio::test]
+    async fn broadcast_errors() {
+        // The memo of the tx selects the failure of the node
+        let client = mock_client(|path, _, request| match path {
+            "/cosmos.auth.v1beta1.Query/Account" => Err(Status::not_found("account not found")),
+            "/cosmos.tx.v1beta1.Service/Simulate" => {
+                match memo(&SimulateRequest::decode(request).unwrap().tx_bytes).as_str() {
+                    "sequence" => Err(Status::unknown(
+                        "account sequence mismatch, expected 3, got 0: incorrect account sequence",
+                    )),
+                    _ => Err(Status::internal("unexpected simulation")),
+                }
+            }
+            "/cosmos.tx.v1beta1.Service/BroadcastTx" => {
+                match memo(&BroadcastTxRequest::decode(request).unwrap().tx_bytes).as_str() {
+                    "fee" => Ok(BroadcastTxResponse {
+                        tx_response: Some(TxResponse {
+                            txhash: "ABC".to_string(),
+                            codespace: "sdk".to_string(),
+                            code: 13,
+                            raw_log:
+                                "insufficient fees; got: 1uosmo required: 10uosmo: insufficient fee"
+                                    .to_string(),
+                            ..Default::default()
+                        }),
+                    }
+                    .encode_to_vec()),
+                    "unauthorized" => Err(Status::invalid_argument(
+                        "signature verification failed: unauthorized",
+                    )),
+                    _ => Err(Status::unavailable("node is syncing")),
+                }
+            }
+            _ => Err(Status::unimplemented(path.to_string())),
+        })
+        .await;
+
+        let mut wallet = Wallet::from_private_key(
+            client,
+            "2485e33678db4175dc0ecef2d6e1fc493d4a0d7f7ce83324b6ed70afe77f3485",
+            "osmo",
+            CoinType::Cosmos,
+            Decimal::percent(1),
+            Decimal::percent(150),
+            "uosmo",
+        )
+        .await
+        .unwrap();
+        wallet.sequence_retry = RetryPolicy::disabled();
+
+        let msgs = vec![MsgSend {
+            from_address: wallet.account_address.clone(),
+            to_address: wallet.account_address.clone(),
+            amount: vec![Coin {
+                denom: "uosmo".to_string(),
+                amount: "1".to_string(),
+            }],
+        }
+        .build_any()];
+
+        let fee = Fee::from_amount_and_gas(
+            cosmrs::Coin {
+                denom: "uosmo".parse().unwrap(),
+                amount: 1,
+            },
+            100_000u64,
+        );
+
+        // Rejected in the simulation
+        let err = wallet
+            .broadcast_tx(
+                msgs.clone(),
+                None,
+                Some("sequence".to_string()),
+                TxOptions::default(),
+                BroadcastMode::Sync,
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.rejection_kind(), Some(RejectionKind::SequenceMismatch));
+        assert!(err.is_retryable());
+
+        // Rejected in `CheckTx`, reported in the response
+        let response = wallet
+            .broadcast_tx(
+                msgs.clone(),
+                Some(fee.clone()),
+                Some("fee".to_string()),
+                TxOptions::default(),
+                BroadcastMode::Sync,
+            )
+            .await
+            .unwrap();
+        let err = check_tx_response(response.tx_response.unwrap()).unwrap_err();
+        assert_eq!(err.rejection_kind(), Some(RejectionKind::InsufficientFee));
+        assert_eq!(wallet.account_sequence, 0);
+
+        let err = wallet
+            .broadcast_tx_and_wait(
+                msgs.clone(),
+                Some(fee.clone()),
+                Some("fee".to_string()),
+                TxOptions::default(),
+                WaitOptions::default(),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.rejection_kind(), Some(RejectionKind::InsufficientFee));
+        assert!(!err.is_retryable());
+
+        // Rejected by the ante handler with a gRPC status
+        let err = wallet
+            .broadcast_tx(
+                msgs.clone(),
+                Some(fee.clone()),
+                Some("unauthorized".to_string()),
+                TxOptions::default(),
+                BroadcastMode::Sync,
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.rejection_kind(), Some(RejectionKind::Unauthorized));
+
+        let err = wallet
+            .broadcast_tx(
+                msgs,
+                Some(fee),
+                None,
+                TxOptions::default(),
+                BroadcastMode::Sync,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err.grpc_error(), Some(Error::Transport(_))));
+        assert!(err.is_retryable());
+    }
+}
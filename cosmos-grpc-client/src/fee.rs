@@ -0,0 +1,267 @@
+use {
+    crate::{
+        math::{IntoU64, IntoUint128},
+        traits::IntoAnyhowResult,
+        wallet::parse_account_id,
+        AnyResult,
+    },
+    anyhow::{anyhow, bail},
+    cosmrs::{tx::Fee, AccountId, Coin, Denom},
+    cosmwasm_std::{Decimal, Uint128},
+    std::str::FromStr,
+};
+
+/// Values of the [`Wallet`](crate::Wallet) used by a [`FeeStrategy`].
+#[derive(Clone, Debug)]
+pub struct FeeContext {
+    pub gas_price: Decimal,
+    pub gas_adjustment: Decimal,
+    pub gas_denom: String,
+}
+
+impl FeeContext {
+    /// Fee paying `gas_price` for each unit of `gas_limit`.
+    pub fn fee_for_gas(&self, gas_limit: u64) -> AnyResult<Fee> {
+        let amount = gas_limit.as_uint128().checked_mul_ceil(self.gas_price)?;
+
+        Ok(Fee {
+            amount: vec![Coin {
+                denom: Denom::from_str(&self.gas_denom).into_anyresult()?,
+                amount: amount.u128(),
+            }],
+            gas_limit,
+            payer: None,
+            granter: None,
+        })
+    }
+}
+
+/// Compute the [`Fee`] of a tx.
+///
+/// Built-in strategies:
+/// - [`SimulateAndAdjust`]: default, simulate the tx and apply `gas_adjustment`;
+/// - [`FixedGas`]: fixed gas limit, priced with `gas_price`;
+/// - [`Fee`]: fixed fee, also multi-denom;
+/// - [`MaxFeeCapped`]: wrap another strategy, failing if the fee exceeds a cap;
+/// - [`WithFeeGrant`]: wrap another strategy, setting the fee granter and payer.
+pub trait FeeStrategy: Send + Sync {
+    /// If `true`, the tx is simulated and the gas used is passed to [`FeeStrategy::compute_fee`].
+    fn needs_simulation(&self) -> bool;
+
+    fn compute_fee(&self, context: &FeeContext, gas_used: Option<u64>) -> AnyResult<Fee>;
+
+    /// Fee granter and payer set by the strategy, included also in the simulated tx
+    /// so that the gas used by the fee grant is counted.
+    fn fee_grant(&self) -> AnyResult<(Option<AccountId>, Option<AccountId>)> {
+        Ok((None, None))
+    }
+}
+
+/// Simulate the tx, set `gas_limit = gas_used * gas_adjustment` and pay `gas_price` for each unit of the gas limit.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SimulateAndAdjust;
+
+impl FeeStrategy for SimulateAndAdjust {
+    fn needs_simulation(&self) -> bool {
+        true
+    }
+
+    fn compute_fee(&self, context: &FeeContext, gas_used: Option<u64>) -> AnyResult<Fee> {
+        let gas_used = gas_used.ok_or(anyhow!("No gas used provided"))?;
+
+        let gas_limit = gas_used
+            .as_uint128()
+            .checked_mul_ceil(context.gas_adjustment)?
+            .as_u64();
+
+        context.fee_for_gas(gas_limit)
+    }
+}
+
+/// Skip the simulation and use a fixed gas limit, paying `gas_price` for each unit.
+#[derive(Clone, Copy, Debug)]
+pub struct FixedGas(pub u64);
+
+impl FeeStrategy for FixedGas {
+    fn needs_simulation(&self) -> bool {
+        false
+    }
+
+    fn compute_fee(&self, context: &FeeContext, _gas_used: Option<u64>) -> AnyResult<Fee> {
+        context.fee_for_gas(self.0)
+    }
+}
+
+impl FeeStrategy for Fee {
+    fn needs_simulation(&self) -> bool {
+        false
+    }
+
+    fn compute_fee(&self, _context: &FeeContext, _gas_used: Option<u64>) -> AnyResult<Fee> {
+        Ok(self.clone())
+    }
+
+    fn fee_grant(&self) -> AnyResult<(Option<AccountId>, Option<AccountId>)> {
+        Ok((self.granter.clone(), self.payer.clone()))
+    }
+}
+
+/// Use `inner` and fail if the amount of `max_fee.denom` exceeds `max_fee.amount`.
+#[derive(Clone, Debug)]
+pub struct MaxFeeCapped<S> {
+    pub inner: S,
+    pub max_fee: cosmwasm_std::Coin,
+}
+
+impl<S: FeeStrategy> MaxFeeCapped<S> {
+    pub fn new(inner: S, max_fee: cosmwasm_std::Coin) -> MaxFeeCapped<S> {
+        MaxFeeCapped { inner, max_fee }
+    }
+}
+
+impl<S: FeeStrategy> FeeStrategy for MaxFeeCapped<S> {
+    fn needs_simulation(&self) -> bool {
+        self.inner.needs_simulation()
+    }
+
+    fn compute_fee(&self, context: &FeeContext, gas_used: Option<u64>) -> AnyResult<Fee> {
+        let fee = self.inner.compute_fee(context, gas_used)?;
+
+        for coin in &fee.amount {
+            if coin.denom.as_ref() == self.max_fee.denom
+                && Uint128::from(coin.amount) > self.max_fee.amount
+            {
+                bail!(
+                    "Fee {}{} exceeds the max fee {}",
+                    coin.amount,
+                    coin.denom,
+                    self.max_fee
+                )
+            }
+        }
+
+        Ok(fee)
+    }
+
+    fn fee_grant(&self) -> AnyResult<(Option<AccountId>, Option<AccountId>)> {
+        self.inner.fee_grant()
+    }
+}
+
+/// Use `inner` and set the fee granter and/or payer, overriding the ones of the [`Wallet`](crate::Wallet).
+///
+/// ## Example:
+/// ``` ignore
+/// let fee = WithFeeGrant::new(SimulateAndAdjust).granter("osmo1...");
+///
+/// wallet
+///     .broadcast_tx_with_fee(msgs, &fee, None, TxOptions::default(), BroadcastMode::Sync)
+///     .await?;
+/// ```
+#[derive(Clone, Debug)]
+pub struct WithFeeGrant<S> {
+    pub inner: S,
+    pub granter: Option<String>,
+    pub payer: Option<String>,
+}
+
+impl<S: FeeStrategy> WithFeeGrant<S> {
+    pub fn new(inner: S) -> WithFeeGrant<S> {
+        WithFeeGrant {
+            inner,
+            granter: None,
+            payer: None,
+        }
+    }
+
+    pub fn granter(mut self, granter: impl Into<String>) -> Self {
+        self.granter = Some(granter.into());
+        self
+    }
+
+    /// The payer must sign the tx, so only the address of the wallet is accepted.
+    pub fn payer(mut self, payer: impl Into<String>) -> Self {
+        self.payer = Some(payer.into());
+        self
+    }
+}
+
+impl<S: FeeStrategy> FeeStrategy for WithFeeGrant<S> {
+    fn needs_simulation(&self) -> bool {
+        self.inner.needs_simulation()
+    }
+
+    fn compute_fee(&self, context: &FeeContext, gas_used: Option<u64>) -> AnyResult<Fee> {
+        let mut fee = self.inner.compute_fee(context, gas_used)?;
+        let (granter, payer) = self.fee_grant()?;
+
+        fee.granter = granter.or(fee.granter);
+        fee.payer = payer.or(fee.payer);
+
+        Ok(fee)
+    }
+
+    fn fee_grant(&self) -> AnyResult<(Option<AccountId>, Option<AccountId>)> {
+        let (granter, payer) = self.inner.fee_grant()?;
+
+        Ok((
+            parse_account_id(self.granter.as_deref())?.or(granter),
+            parse_account_id(self.payer.as_deref())?.or(payer),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::{FeeContext, FeeStrategy, FixedGas, MaxFeeCapped, SimulateAndAdjust, WithFeeGrant},
+        cosmwasm_std::{coin, Decimal},
+        std::str::FromStr,
+    };
+
+    #[test]
+    fn fee_strategies() {
+        let context = FeeContext {
+            gas_price: Decimal::from_str("0.025").unwrap(),
+            gas_adjustment: Decimal::from_str("1.5").unwrap(),
+            gas_denom: "uosmo".to_string(),
+        };
+
+        let fee = SimulateAndAdjust
+            .compute_fee(&context, Some(100_001))
+            .unwrap();
+        assert_eq!(fee.gas_limit, 150_002);
+        assert_eq!(fee.amount[0].amount, 3_751);
+
+        let fee = FixedGas(200_000).compute_fee(&context, None).unwrap();
+        assert_eq!(fee.gas_limit, 200_000);
+        assert_eq!(fee.amount[0].amount, 5_000);
+
+        let capped = MaxFeeCapped::new(FixedGas(200_000), coin(4_000, "uosmo"));
+        assert!(capped.compute_fee(&context, None).is_err());
+
+        let capped = MaxFeeCapped::new(FixedGas(200_000), coin(5_000, "uosmo"));
+        assert!(!capped.needs_simulation());
+        assert!(capped.compute_fee(&context, None).is_ok());
+
+        let granter = "osmo1ll3s59aawh0qydpz2q3xmqf6pwzmj24t9ch58c";
+        let grant = MaxFeeCapped::new(
+            WithFeeGrant::new(SimulateAndAdjust).granter(granter),
+            coin(5_000, "uosmo"),
+        );
+        assert!(grant.needs_simulation());
+
+        let (fee_granter, fee_payer) = grant.fee_grant().unwrap();
+        assert_eq!(fee_granter.unwrap().as_ref(), granter);
+        assert!(fee_payer.is_none());
+        assert_eq!(
+            grant
+                .compute_fee(&context, Some(100_000))
+                .unwrap()
+                .granter
+                .unwrap()
+                .as_ref(),
+            granter
+        );
+    }
+}
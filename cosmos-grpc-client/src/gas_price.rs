@@ -0,0 +1,245 @@
+use {
+    crate::{client::GrpcClient, math::sdk_dec_to_decimal, AnyResult},
+    anyhow::anyhow,
+    cosmos_sdk_proto::cosmos::base::v1beta1::DecCoin as ProtoDecCoin,
+    cosmwasm_std::Decimal,
+    std::{
+        str::FromStr,
+        sync::{Arc, Mutex},
+        time::{Duration, Instant},
+    },
+};
+
+/// `cosmos.base.node.v1beta1.ConfigRequest`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct ConfigRequest {}
+
+/// `cosmos.base.node.v1beta1.ConfigResponse`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct ConfigResponse {
+    #[prost(string, tag = "1")]
+    minimum_gas_price: String,
+}
+
+/// `feemarket.feemarket.v1.GasPriceRequest`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct GasPriceRequest {
+    #[prost(string, tag = "1")]
+    denom: String,
+}
+
+/// `feemarket.feemarket.v1.GasPriceResponse`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct GasPriceResponse {
+    #[prost(message, optional, tag = "1")]
+    price: Option<ProtoDecCoin>,
+}
+
+/// Source queried by [`GasPriceOracle`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GasPriceSource {
+    /// `minimum-gas-prices` of the node (`cosmos.base.node.v1beta1.Service/Config`).
+    NodeConfig,
+    /// EIP-1559 base fee of the Osmosis `txfees` module, available only for the base denom of the chain.
+    #[cfg(feature = "osmosis")]
+    OsmosisEipBaseFee,
+    /// Gas price of the `feemarket` module.
+    FeeMarket,
+}
+
+impl GasPriceSource {
+    /// All the sources available with the enabled features.
+    pub fn all() -> Vec<GasPriceSource> {
+        vec![
+            GasPriceSource::NodeConfig,
+            #[cfg(feature = "osmosis")]
+            GasPriceSource::OsmosisEipBaseFee,
+            GasPriceSource::FeeMarket,
+        ]
+    }
+}
+
+/// Discover the gas price of `denom` querying the fee modules of the chain.
+///
+/// The highest price among the available sources is used, and cached for `ttl` (default 30 seconds).
+/// Set it as `gas_price_oracle` of a [`Wallet`](crate::Wallet) to use it in the fee calculation.
+///
+/// ## Example:
+/// ``` ignore
+/// wallet.gas_price_oracle = Some(
+///     GasPriceOracle::new(client.clone(), "uosmo").ttl(Duration::from_secs(10)),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct GasPriceOracle {
+    client: GrpcClient,
+    denom: String,
+    sources: Vec<GasPriceSource>,
+    ttl: Duration,
+    cache: Arc<Mutex<Option<(Instant, Decimal)>>>,
+}
+
+impl GasPriceOracle {
+    pub fn new(client: GrpcClient, denom: impl Into<String>) -> GasPriceOracle {
+        GasPriceOracle {
+            client,
+            denom: denom.into(),
+            sources: GasPriceSource::all(),
+            ttl: Duration::from_secs(30),
+            cache: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn sources(mut self, sources: Vec<GasPriceSource>) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    pub fn denom(&self) -> &str {
+        &self.denom
+    }
+
+    /// Cached gas price, queried again if older than `ttl`.
+    pub async fn gas_price(&self) -> AnyResult<Decimal> {
+        if let Some((updated_at, price)) = *self.cache.lock().unwrap() {
+            if updated_at.elapsed() < self.ttl {
+                return Ok(price);
+            }
+        }
+
+        self.refresh().await
+    }
+
+    /// Query all the sources, ignoring the cache.
+    pub async fn refresh(&self) -> AnyResult<Decimal> {
+        let mut prices = vec![];
+        let mut errors = vec![];
+
+        for source in &self.sources {
+            match self.query(*source).await {
+                Ok(Some(price)) => prices.push(price),
+                Ok(None) => errors.push(format!("{source:?}: no price for {}", self.denom)),
+                Err(err) => errors.push(format!("{source:?}: {err:#}")),
+            }
+        }
+
+        let price = prices.into_iter().max().ok_or(anyhow!(
+            "No gas price available for {}: {}",
+            self.denom,
+            errors.join(", ")
+        ))?;
+
+        *self.cache.lock().unwrap() = Some((Instant::now(), price));
+
+        Ok(price)
+    }
+
+    async fn query(&self, source: GasPriceSource) -> AnyResult<Option<Decimal>> {
+        match source {
+            GasPriceSource::NodeConfig => {
+                let response: ConfigResponse = self
+                    .client
+                    .proto_query(ConfigRequest {}, "/cosmos.base.node.v1beta1.Service/Config")
+                    .await?;
+
+                parse_dec_coins(&response.minimum_gas_price, &self.denom)
+            }
+            #[cfg(feature = "osmosis")]
+            GasPriceSource::OsmosisEipBaseFee => {
+                use osmosis_std::types::osmosis::txfees::v1beta1::{
+                    QueryBaseDenomRequest, QueryBaseDenomResponse, QueryEipBaseFeeRequest,
+                    QueryEipBaseFeeResponse,
+                };
+
+                // The base fee is expressed only in the base denom of the chain
+                let base_denom: QueryBaseDenomResponse = self
+                    .client
+                    .proto_query(
+                        QueryBaseDenomRequest {},
+                        "/osmosis.txfees.v1beta1.Query/BaseDenom",
+                    )
+                    .await?;
+
+                if base_denom.base_denom != self.denom {
+                    return Ok(None);
+                }
+
+                let response: QueryEipBaseFeeResponse = self
+                    .client
+                    .proto_query(
+                        QueryEipBaseFeeRequest {},
+                        "/osmosis.txfees.v1beta1.Query/GetEipBaseFee",
+                    )
+                    .await?;
+
+                Ok(Some(sdk_dec_to_decimal(&response.base_fee)?))
+            }
+            GasPriceSource::FeeMarket => {
+                let response: GasPriceResponse = self
+                    .client
+                    .proto_query(
+                        GasPriceRequest {
+                            denom: self.denom.clone(),
+                        },
+                        "/feemarket.feemarket.v1.Query/GasPrice",
+                    )
+                    .await?;
+
+                response
+                    .price
+                    .filter(|price| price.denom == self.denom)
+                    .map(|price| sdk_dec_to_decimal(&price.amount))
+                    .transpose()
+            }
+        }
+    }
+}
+
+/// Parse the price of `denom` from a list of dec coins, e.g. `0.0025uosmo,0.1uatom`.
+fn parse_dec_coins(value: &str, denom: &str) -> AnyResult<Option<Decimal>> {
+    for coin in value
+        .split(',')
+        .map(str::trim)
+        .filter(|coin| !coin.is_empty())
+    {
+        let split = coin
+            .find(|char: char| !char.is_ascii_digit() && char != '.')
+            .ok_or(anyhow!("Invalid dec coin: {coin}"))?;
+
+        if &coin[split..] == denom {
+            return Ok(Some(Decimal::from_str(&coin[..split])?));
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod test {
+    use {super::parse_dec_coins, cosmwasm_std::Decimal, std::str::FromStr};
+
+    #[test]
+    fn min_gas_prices() {
+        let prices = "0.002500000000000000uosmo,0.1ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2";
+
+        assert_eq!(
+            parse_dec_coins(prices, "uosmo").unwrap(),
+            Some(Decimal::from_str("0.0025").unwrap())
+        );
+        assert_eq!(
+            parse_dec_coins(
+                prices,
+                "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+            )
+            .unwrap(),
+            Some(Decimal::from_str("0.1").unwrap())
+        );
+        assert_eq!(parse_dec_coins("", "uosmo").unwrap(), None);
+        assert_eq!(parse_dec_coins(prices, "uatom").unwrap(), None);
+    }
+}
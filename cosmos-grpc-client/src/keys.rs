@@ -0,0 +1,181 @@
+use {
+    crate::{traits::IntoAnyhowResult, AnyResult},
+    anyhow::{anyhow, bail},
+    bip32::{
+        secp256k1::{
+            ecdsa::SigningKey,
+            elliptic_curve::rand_core::{OsRng, RngCore},
+        },
+        DerivationPath, XPrv,
+    },
+    bip39::Mnemonic,
+    std::str::FromStr,
+};
+
+/// Passphrase and derivation path used to derive the key of a [`Wallet`](crate::Wallet) from a mnemonic.
+///
+/// The default path is `m/44'/{coin_type}'/{account}'/{change}/{index}`, a `u64` is converted into the `index`.
+/// ## Example:
+/// ``` ignore
+/// // Ledger Live style path, m/44'/118'/2'/0/0
+/// let options = SeedOptions::default().account(2);
+///
+/// // 25th word and custom path
+/// let options = SeedOptions::default()
+///     .passphrase("secret")
+///     .derivation_path(DerivationPath::from_str("m/44'/118'/0'/0/7")?);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SeedOptions {
+    /// BIP-39 passphrase, also known as 25th word.
+    pub passphrase: Option<String>,
+    pub account: u64,
+    pub change: u64,
+    pub index: u64,
+    /// Full derivation path, overriding coin type, `account`, `change` and `index`.
+    pub derivation_path: Option<DerivationPath>,
+}
+
+impl SeedOptions {
+    pub fn passphrase(mut self, passphrase: impl Into<String>) -> Self {
+        self.passphrase = Some(passphrase.into());
+        self
+    }
+
+    pub fn account(mut self, account: u64) -> Self {
+        self.account = account;
+        self
+    }
+
+    pub fn change(mut self, change: u64) -> Self {
+        self.change = change;
+        self
+    }
+
+    pub fn index(mut self, index: u64) -> Self {
+        self.index = index;
+        self
+    }
+
+    pub fn derivation_path(mut self, derivation_path: DerivationPath) -> Self {
+        self.derivation_path = Some(derivation_path);
+        self
+    }
+
+    /// Derivation path of the key for `coin_type`.
+    pub fn path(&self, coin_type: u64) -> AnyResult<DerivationPath> {
+        match &self.derivation_path {
+            Some(derivation_path) => Ok(derivation_path.clone()),
+            None => Ok(DerivationPath::from_str(&format!(
+                "m/44'/{coin_type}'/{}'/{}/{}",
+                self.account, self.change, self.index
+            ))?),
+        }
+    }
+}
+
+impl From<u64> for SeedOptions {
+    fn from(index: u64) -> Self {
+        SeedOptions::default().index(index)
+    }
+}
+
+/// Generate a random BIP-39 mnemonic of `word_count` words (12, 15, 18, 21 or 24).
+pub(crate) fn generate_mnemonic(word_count: usize) -> AnyResult<Mnemonic> {
+    if !matches!(word_count, 12 | 15 | 18 | 21 | 24) {
+        bail!("Invalid mnemonic length {word_count}, expected 12, 15, 18, 21 or 24 words")
+    }
+
+    // Each word encodes 11 bits, 1 bit every 33 is the checksum
+    let mut entropy = vec![0_u8; word_count / 3 * 4];
+    OsRng.fill_bytes(&mut entropy);
+
+    Ok(Mnemonic::from_entropy(&entropy)?)
+}
+
+/// Derive the key of `coin_type` at the path of `options`.
+pub(crate) fn derive_sign_key(
+    mnemonic: &Mnemonic,
+    options: &SeedOptions,
+    coin_type: u64,
+) -> AnyResult<SigningKey> {
+    let seed = mnemonic.to_seed(options.passphrase.as_deref().unwrap_or_default());
+    let derivation_path = options.path(coin_type)?;
+
+    let xprv = XPrv::derive_from_path(seed, &derivation_path)
+        .map_err(|err| anyhow!("Failed to derive the key at {derivation_path}: {err}"))?;
+
+    Ok(xprv.private_key().clone())
+}
+
+/// Parse a hex encoded private key, with or without the `0x` prefix.
+pub(crate) fn parse_private_key(private_key: &str) -> AnyResult<SigningKey> {
+    let bytes = subtle_encoding::hex::decode(private_key.trim().trim_start_matches("0x"))
+        .into_anyresult()?;
+
+    SigningKey::from_slice(&bytes).into_anyresult()
+}
+
+pub(crate) fn private_key_to_hex(sign_key: &SigningKey) -> String {
+    String::from_utf8(subtle_encoding::hex::encode(sign_key.to_bytes())).unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::{
+            derive_sign_key, generate_mnemonic, parse_private_key, private_key_to_hex, SeedOptions,
+        },
+        bip32::DerivationPath,
+        bip39::Mnemonic,
+        std::str::FromStr,
+    };
+
+    #[test]
+    fn mnemonic_and_private_key() {
+        assert_eq!(generate_mnemonic(12).unwrap().word_count(), 12);
+        assert_eq!(generate_mnemonic(24).unwrap().word_count(), 24);
+        assert!(generate_mnemonic(13).is_err());
+
+        let mnemonic = Mnemonic::from_str(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        )
+        .unwrap();
+
+        let sign_key = derive_sign_key(&mnemonic, &SeedOptions::default(), 118).unwrap();
+        let address = cosmrs::crypto::secp256k1::SigningKey::new(Box::new(sign_key.clone()))
+            .public_key()
+            .account_id("cosmos")
+            .unwrap();
+        assert_eq!(
+            address.to_string(),
+            "cosmos19rl4cm2hmr8afy4kldpxz3fka4jguq0auqdal4"
+        );
+
+        let hex = private_key_to_hex(&sign_key);
+
+        assert_eq!(parse_private_key(&hex).unwrap(), sign_key);
+        assert_eq!(parse_private_key(&format!("0x{hex}")).unwrap(), sign_key);
+        assert_ne!(
+            derive_sign_key(&mnemonic, &SeedOptions::default().passphrase("secret"), 118).unwrap(),
+            sign_key
+        );
+        assert_ne!(
+            derive_sign_key(&mnemonic, &1.into(), 118).unwrap(),
+            sign_key
+        );
+
+        let options = SeedOptions::default().account(2).change(1).index(3);
+        assert_eq!(options.path(118).unwrap().to_string(), "m/44'/118'/2'/1/3");
+        assert_eq!(
+            derive_sign_key(&mnemonic, &options, 118).unwrap(),
+            derive_sign_key(
+                &mnemonic,
+                &SeedOptions::default()
+                    .derivation_path(DerivationPath::from_str("m/44'/118'/2'/1/3").unwrap()),
+                60
+            )
+            .unwrap()
+        );
+    }
+}
@@ -1,17 +1,87 @@
+//! Cosmos gRPC client, with a [`Wallet`] to sign and broadcast txs.
+//!
+//! ## Errors
+//!
+//! The methods of [`GrpcClient`], [`Wallet`] and [`SharedWallet`] return an [`AnyResult`].
+//! When the failure comes from the node or the chain, the [`anyhow::Error`] carries a typed [`Error`],
+//! also under added contexts. Extract it with [`ErrorExt`]:
+//! - [`Error::Transport`] if the node can't be reached, see [`ErrorExt::is_retryable`].
+//! - [`Error::Status`] for the other gRPC statuses, e.g. `NotFound` (see [`ErrorExt::is_not_found`]).
+//! - [`Error::Decode`] if a response can't be decoded.
+//! - [`Error::ChainRejection`] if the tx is rejected in the simulation, or by the ante handler
+//!   when broadcasting. Its [`RejectionKind`] is available with [`ErrorExt::rejection_kind`].
+//!
+//! A tx rejected in `CheckTx` doesn't make [`Wallet::broadcast_tx`] fail, as the `code` is in the
+//! returned response: check it with [`check_tx_response`], or use [`Wallet::broadcast_tx_and_wait`].
+//!
+//! ``` ignore
+//! match wallet.broadcast_tx_and_wait(msgs, None, None, TxOptions::default(), WaitOptions::default()).await {
+//!     Err(err) if err.rejection_kind() == Some(RejectionKind::InsufficientFee) => { /* raise the gas price */ }
+//!     Err(err) if err.is_retryable() => { /* try again later */ }
+//!     res => { res?; }
+//! }
+//! ```
+
+mod batcher;
+mod broadcast;
+mod builder;
+mod channel;
 mod client;
 mod definitions;
 mod errors;
+mod fee;
+mod gas_price;
+mod keys;
+mod keystore;
 mod math;
+#[cfg(test)]
+mod mock;
+mod modules;
+mod pagination;
+mod sequence;
+mod shared_wallet;
 mod traits;
+mod tx_options;
 mod wallet;
 
 pub use {
-    crate::client::GrpcClient,
-    crate::definitions::{BroadcastMode, CoinType, LOCAL_NODE_GPRC},
+    crate::batcher::{BatchMsgError, BatchOptions, BatchReceipt, MessageBatcher},
+    crate::broadcast::{check_tx_response, WaitOptions},
+    crate::builder::{GrpcClientBuilder, DEFAULT_MAX_DECODING_MESSAGE_SIZE},
+    crate::channel::{EndpointHealth, EndpointStatus, GrpcChannel, BLOCK_HEIGHT_METADATA},
+    crate::client::{GrpcClient, StandardClients},
+    crate::definitions::{
+        BroadcastMode, CoinType, EthKeyConfig, KeyAlgorithm, KeyConfig, LOCAL_NODE_GPRC,
+    },
+    crate::errors::{Error, ErrorExt, RejectionKind, SDK_CODESPACE},
+    crate::fee::{
+        FeeContext, FeeStrategy, FixedGas, MaxFeeCapped, SimulateAndAdjust, WithFeeGrant,
+    },
+    crate::gas_price::{GasPriceOracle, GasPriceSource},
+    crate::keys::SeedOptions,
+    crate::keystore::{Keystore, BCRYPT_SECURITY_PARAMETER},
+    crate::math::{sdk_dec_to_decimal, sdk_dec_to_decimal256},
+    crate::modules::{
+        auth::AccountDecoder,
+        authz::{ContractExecutionFilter, ContractExecutionLimit, ContractGrant},
+        distribution::DelegatorRewards,
+        gov::{GovV1MsgSubmitProposal, Tally},
+        staking::{
+            DelegationInfo, RedelegationEntryInfo, RedelegationInfo, UnbondingEntryInfo,
+            UnbondingInfo, ValidatorInfo,
+        },
+    },
+    crate::pagination::{
+        query_all, query_stream, PaginatedRequest, PaginatedResponse, PaginationOptions,
+    },
+    crate::sequence::{RetryPolicy, SEQUENCE_MISMATCH_CODE},
     anyhow::Result as AnyResult,
+    bip32::DerivationPath,
     cosmos_sdk_proto, cosmrs,
-    cosmwasm_std::{Decimal, StdError, StdResult, Uint128},
+    cosmwasm_std::{DecCoin, Decimal, Decimal256, StdError, StdResult, Uint128},
+    shared_wallet::SharedWallet,
     traits::*,
+    tx_options::TxOptions,
     wallet::Wallet,
 };
 
@@ -1,4 +1,11 @@
-use cosmwasm_std::Uint128;
+use {
+    crate::AnyResult,
+    cosmwasm_std::{Coin, DecCoin, Decimal, Decimal256, Uint128, Uint256},
+    std::str::FromStr,
+};
+
+/// Decimal places of the cosmos-sdk `LegacyDec`.
+const SDK_DEC_PLACES: u32 = 18;
 
 pub trait IntoUint128 {
     fn as_uint128(&self) -> Uint128;
@@ -19,3 +26,99 @@ impl IntoU64 for Uint128 {
         self.u128().try_into().unwrap()
     }
 }
+
+pub trait TryIntoStdCoin {
+    fn try_into_std_coin(self) -> AnyResult<Coin>;
+}
+
+impl TryIntoStdCoin for cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
+    fn try_into_std_coin(self) -> AnyResult<Coin> {
+        Ok(Coin {
+            amount: Uint128::from_str(&self.amount)?,
+            denom: self.denom,
+        })
+    }
+}
+
+pub trait IntoProtoCoin {
+    fn into_proto_coin(self) -> cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+}
+
+impl IntoProtoCoin for Coin {
+    fn into_proto_coin(self) -> cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
+        cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
+            denom: self.denom,
+            amount: self.amount.to_string(),
+        }
+    }
+}
+
+pub trait TryIntoStdDecCoin {
+    fn try_into_std_dec_coin(self) -> AnyResult<DecCoin>;
+}
+
+impl TryIntoStdDecCoin for cosmos_sdk_proto::cosmos::base::v1beta1::DecCoin {
+    fn try_into_std_dec_coin(self) -> AnyResult<DecCoin> {
+        Ok(DecCoin {
+            amount: sdk_dec_to_decimal256(&self.amount)?,
+            denom: self.denom,
+        })
+    }
+}
+
+/// Parse a cosmos-sdk `LegacyDec`.
+///
+/// In protobuf encoding the value is the integer representation with 18 decimal places
+/// (`"1500000000000000000"` = 1.5), while the human readable form contains the decimal point.
+pub fn sdk_dec_to_decimal256(value: &str) -> AnyResult<Decimal256> {
+    if value.is_empty() {
+        Ok(Decimal256::zero())
+    } else if value.contains('.') {
+        Ok(Decimal256::from_str(value)?)
+    } else {
+        Ok(Decimal256::from_atomics(
+            Uint256::from_str(value)?,
+            SDK_DEC_PLACES,
+        )?)
+    }
+}
+
+/// Same as [`sdk_dec_to_decimal256`], for values fitting into a [`Decimal`] (rates, percentages).
+pub fn sdk_dec_to_decimal(value: &str) -> AnyResult<Decimal> {
+    if value.is_empty() {
+        Ok(Decimal::zero())
+    } else if value.contains('.') {
+        Ok(Decimal::from_str(value)?)
+    } else {
+        Ok(Decimal::from_atomics(
+            Uint128::from_str(value)?,
+            SDK_DEC_PLACES,
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use cosmwasm_std::{Decimal, Decimal256};
+
+    use super::{sdk_dec_to_decimal, sdk_dec_to_decimal256};
+
+    #[test]
+    fn sdk_dec() {
+        assert_eq!(
+            sdk_dec_to_decimal("50000000000000000").unwrap(),
+            Decimal::from_str("0.05").unwrap()
+        );
+        assert_eq!(
+            sdk_dec_to_decimal("0.05").unwrap(),
+            Decimal::from_str("0.05").unwrap()
+        );
+        assert_eq!(
+            sdk_dec_to_decimal256("1234567890000000000000000000000").unwrap(),
+            Decimal256::from_str("1234567890000").unwrap()
+        );
+        assert_eq!(sdk_dec_to_decimal256("").unwrap(), Decimal256::zero());
+    }
+}
@@ -0,0 +1,251 @@
+// Handlers return a `tonic::Status`, as the generated servers
+#![allow(clippy::result_large_err)]
+
+use {
+    crate::{
+        builder::MessageLimits, channel::GrpcChannel, client::GrpcClient, definitions::CoinType,
+        wallet::Wallet,
+    },
+    cosmos_sdk_proto::{
+        cosmos::{
+            base::{
+                abci::v1beta1::{GasInfo, TxResponse},
+                tendermint::v1beta1::{Block, GetLatestBlockResponse, GetNodeInfoResponse, Header},
+            },
+            tx::v1beta1::{AuthInfo, BroadcastTxResponse, SimulateResponse, TxBody, TxRaw},
+        },
+        tendermint::v0_34::p2p::DefaultNodeInfo,
+    },
+    cosmrs::tx::Fee,
+    cosmwasm_std::Decimal,
+    prost::{
+        bytes::{Buf, BufMut},
+        Message,
+    },
+    std::{
+        convert::Infallible,
+        future::{ready, Ready},
+        net::TcpListener,
+        sync::Arc,
+    },
+    tonic::{
+        codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder},
+        metadata::MetadataMap,
+        server::{Grpc, UnaryService},
+        transport::Channel,
+        Request, Response, Status,
+    },
+};
+
+type Handler = Arc<dyn Fn(&str, &MetadataMap, &[u8]) -> Result<Vec<u8>, Status> + Send + Sync>;
+
+/// Start a local grpc node answering every unary request with `handler`,
+/// called with the path, the metadata and the encoded request. Returns the address of the node.
+pub(crate) fn mock_node(
+    handler: impl Fn(&str, &MetadataMap, &[u8]) -> Result<Vec<u8>, Status> + Send + Sync + 'static,
+) -> String {
+    let handler: Handler = Arc::new(handler);
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.set_nonblocking(true).unwrap();
+    let address = listener.local_addr().unwrap();
+
+    let make_service = hyper::service::make_service_fn(move |_| {
+        let handler = handler.clone();
+
+        async move {
+            Ok::<_, Infallible>(hyper::service::service_fn(
+                move |request: hyper::Request<hyper::Body>| {
+                    let service = RawService {
+                        path: request.uri().path().to_string(),
+                        handler: handler.clone(),
+                    };
+
+                    async move { Ok::<_, Infallible>(Grpc::new(RawCodec).unary(service, request).await) }
+                },
+            ))
+        }
+    });
+
+    tokio::spawn(
+        hyper::Server::from_tcp(listener)
+            .unwrap()
+            .http2_only(true)
+            .serve(make_service),
+    );
+
+    format!("http://{address}")
+}
+
+/// Lazy channel to `address`.
+pub(crate) fn mock_channel(address: &str) -> Channel {
+    Channel::from_shared(address.to_string())
+        .unwrap()
+        .connect_lazy()
+}
+
+/// Chain id reported by the node of [`mock_client`].
+pub(crate) const MOCK_CHAIN_ID: &str = "mock-1";
+
+/// [`GrpcClient`] connected to a [`mock_node`], answering `GetNodeInfo` and forwarding every other request to `handler`.
+pub(crate) async fn mock_client(
+    handler: impl Fn(&str, &MetadataMap, &[u8]) -> Result<Vec<u8>, Status> + Send + Sync + 'static,
+) -> GrpcClient {
+    let address = mock_node(move |path, metadata, request| match path {
+        "/cosmos.base.tendermint.v1beta1.Service/GetNodeInfo" => Ok(GetNodeInfoResponse {
+            default_node_info: Some(DefaultNodeInfo {
+                network: MOCK_CHAIN_ID.to_string(),
+                ..Default::default()
+            }),
+            application_version: None,
+        }
+        .encode_to_vec()),
+        _ => handler(path, metadata, request),
+    });
+
+    GrpcClient::build(
+        GrpcChannel::single(&address, mock_channel(&address)),
+        MessageLimits::default(),
+    )
+    .await
+    .unwrap()
+}
+
+/// Encoded `GetLatestBlock` response at `height`.
+pub(crate) fn latest_block(height: i64) -> Vec<u8> {
+    GetLatestBlockResponse {
+        sdk_block: Some(Block {
+            header: Some(Header {
+                height,
+                chain_id: MOCK_CHAIN_ID.to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+    .encode_to_vec()
+}
+
+/// [`Wallet`] of a new account (number and sequence 0) on a [`mock_client`],
+/// forwarding every request other than the account query to `handler`.
+pub(crate) async fn mock_wallet(
+    handler: impl Fn(&str, &MetadataMap, &[u8]) -> Result<Vec<u8>, Status> + Send + Sync + 'static,
+) -> Wallet {
+    let client = mock_client(move |path, metadata, request| match path {
+        "/cosmos.auth.v1beta1.Query/Account" => Err(Status::not_found("account not found")),
+        _ => handler(path, metadata, request),
+    })
+    .await;
+
+    Wallet::from_private_key(
+        client,
+        "2485e33678db4175dc0ecef2d6e1fc493d4a0d7f7ce83324b6ed70afe77f3485",
+        "osmo",
+        CoinType::Cosmos,
+        Decimal::percent(1),
+        Decimal::percent(150),
+        "uosmo",
+    )
+    .await
+    .unwrap()
+}
+
+/// Fee skipping the simulation.
+pub(crate) fn mock_fee() -> Fee {
+    Fee::from_amount_and_gas(
+        cosmrs::Coin {
+            denom: "uosmo".parse().unwrap(),
+            amount: 1000,
+        },
+        100_000u64,
+    )
+}
+
+/// Body and sequence of a signed tx.
+pub(crate) fn decode_tx(tx_bytes: &[u8]) -> (TxBody, u64) {
+    let tx = TxRaw::decode(tx_bytes).unwrap();
+    let auth_info = AuthInfo::decode(tx.auth_info_bytes.as_slice()).unwrap();
+
+    (
+        TxBody::decode(tx.body_bytes.as_slice()).unwrap(),
+        auth_info.signer_infos[0].sequence,
+    )
+}
+
+/// Encoded `Simulate` response.
+pub(crate) fn simulate_response(gas_used: u64) -> Vec<u8> {
+    SimulateResponse {
+        gas_info: Some(GasInfo {
+            gas_wanted: 0,
+            gas_used,
+        }),
+        result: None,
+    }
+    .encode_to_vec()
+}
+
+/// Encoded `BroadcastTx` response with the `CheckTx` result.
+pub(crate) fn broadcast_response(txhash: &str, code: u32, raw_log: &str) -> Vec<u8> {
+    BroadcastTxResponse {
+        tx_response: Some(TxResponse {
+            txhash: txhash.to_string(),
+            codespace: if code == 0 { "" } else { "sdk" }.to_string(),
+            code,
+            raw_log: raw_log.to_string(),
+            ..Default::default()
+        }),
+    }
+    .encode_to_vec()
+}
+
+struct RawService {
+    path: String,
+    handler: Handler,
+}
+
+impl UnaryService<Vec<u8>> for RawService {
+    type Response = Vec<u8>;
+    type Future = Ready<Result<Response<Vec<u8>>, Status>>;
+
+    fn call(&mut self, request: Request<Vec<u8>>) -> Self::Future {
+        ready((self.handler)(&self.path, request.metadata(), request.get_ref()).map(Response::new))
+    }
+}
+
+/// Codec passing the encoded messages as they are.
+struct RawCodec;
+
+impl Codec for RawCodec {
+    type Encode = Vec<u8>;
+    type Decode = Vec<u8>;
+    type Encoder = RawCodec;
+    type Decoder = RawCodec;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        RawCodec
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        RawCodec
+    }
+}
+
+impl Encoder for RawCodec {
+    type Item = Vec<u8>;
+    type Error = Status;
+
+    fn encode(&mut self, item: Vec<u8>, dst: &mut EncodeBuf<'_>) -> Result<(), Status> {
+        dst.put_slice(&item);
+        Ok(())
+    }
+}
+
+impl Decoder for RawCodec {
+    type Item = Vec<u8>;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Vec<u8>>, Status> {
+        Ok(Some(src.copy_to_bytes(src.remaining()).to_vec()))
+    }
+}
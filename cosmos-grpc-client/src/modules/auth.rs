@@ -0,0 +1,252 @@
+use {
+    crate::{client::GrpcClient, errors::Error, AnyResult},
+    anyhow::anyhow,
+    cosmos_sdk_proto::cosmos::{
+        auth::v1beta1::{BaseAccount, ModuleAccount, QueryAccountRequest},
+        vesting::v1beta1::{
+            BaseVestingAccount, ContinuousVestingAccount, DelayedVestingAccount,
+            PeriodicVestingAccount, PermanentLockedAccount,
+        },
+    },
+    prost::Message,
+    prost_types::Any,
+    std::{collections::HashMap, fmt::Debug, sync::Arc},
+};
+
+type DecodeFn = Arc<dyn Fn(&[u8]) -> AnyResult<BaseAccount> + Send + Sync>;
+
+/// `EthAccount` of Injective and ethermint, only the `base_account` field is decoded
+/// since `code_hash` has a different type on the two chains.
+#[derive(Clone, PartialEq, prost::Message)]
+struct EthAccount {
+    #[prost(message, optional, tag = "1")]
+    base_account: Option<BaseAccount>,
+}
+
+/// Extract the [`BaseAccount`] from the accounts returned by the `auth` module, based on their type url.
+///
+/// By default `BaseAccount`, `ModuleAccount`, the vesting accounts and the `EthAccount` of
+/// Injective and ethermint are supported. Other types can be added with [`AccountDecoder::register`].
+/// ## Example:
+/// ``` ignore
+/// client.account_decoder.register("/stride.vesting.StridePeriodicVestingAccount", |bytes| {
+///     let account = StridePeriodicVestingAccount::decode(bytes)?;
+///     account.base_vesting_account.and_then(|val| val.base_account).ok_or(anyhow!("No base account"))
+/// });
+/// ```
+#[derive(Clone)]
+pub struct AccountDecoder {
+    decoders: HashMap<String, DecodeFn>,
+}
+
+impl Default for AccountDecoder {
+    fn default() -> Self {
+        let mut decoder = AccountDecoder {
+            decoders: HashMap::new(),
+        };
+
+        decoder
+            .register("/cosmos.auth.v1beta1.BaseAccount", |bytes| {
+                Ok(BaseAccount::decode(bytes)?)
+            })
+            .register("/cosmos.auth.v1beta1.ModuleAccount", |bytes| {
+                base_account(ModuleAccount::decode(bytes)?.base_account)
+            })
+            .register(
+                "/cosmos.vesting.v1beta1.ContinuousVestingAccount",
+                |bytes| {
+                    vesting_base_account(
+                        ContinuousVestingAccount::decode(bytes)?.base_vesting_account,
+                    )
+                },
+            )
+            .register("/cosmos.vesting.v1beta1.DelayedVestingAccount", |bytes| {
+                vesting_base_account(DelayedVestingAccount::decode(bytes)?.base_vesting_account)
+            })
+            .register("/cosmos.vesting.v1beta1.PeriodicVestingAccount", |bytes| {
+                vesting_base_account(PeriodicVestingAccount::decode(bytes)?.base_vesting_account)
+            })
+            .register("/cosmos.vesting.v1beta1.PermanentLockedAccount", |bytes| {
+                vesting_base_account(PermanentLockedAccount::decode(bytes)?.base_vesting_account)
+            })
+            .register("/injective.types.v1beta1.EthAccount", decode_eth_account)
+            .register("/ethermint.types.v1.EthAccount", decode_eth_account);
+
+        decoder
+    }
+}
+
+impl AccountDecoder {
+    /// Register the decoder of the accounts with `type_url`, replacing the existing one.
+    pub fn register(
+        &mut self,
+        type_url: impl Into<String>,
+        decoder: impl Fn(&[u8]) -> AnyResult<BaseAccount> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.decoders.insert(type_url.into(), Arc::new(decoder));
+        self
+    }
+
+    pub fn decode(&self, account: &Any) -> AnyResult<BaseAccount> {
+        let decoder = self.decoders.get(&account.type_url).ok_or(anyhow!(
+            "Unknown account type {}, register it in the AccountDecoder",
+            account.type_url
+        ))?;
+
+        decoder(&account.value)
+    }
+}
+
+impl Debug for AccountDecoder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AccountDecoder")
+            .field("type_urls", &self.decoders.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+pub(crate) fn decode_eth_account(bytes: &[u8]) -> AnyResult<BaseAccount> {
+    base_account(EthAccount::decode(bytes)?.base_account)
+}
+
+fn base_account(account: Option<BaseAccount>) -> AnyResult<BaseAccount> {
+    account.ok_or(anyhow!("No base_account in account"))
+}
+
+fn vesting_base_account(account: Option<BaseVestingAccount>) -> AnyResult<BaseAccount> {
+    base_account(account.and_then(|account| account.base_account))
+}
+
+/// Helpers for the `auth` module.
+impl GrpcClient {
+    /// Account of `address` as returned by the chain, `None` if it doesn't exist yet.
+    pub async fn account(&self, address: impl Into<String>) -> AnyResult<Option<Any>> {
+        let response = self
+            .clients
+            .auth
+            .clone()
+            .account(QueryAccountRequest {
+                address: address.into(),
+            })
+            .await;
+
+        match response {
+            Ok(response) => Ok(Some(
+                response
+                    .into_inner()
+                    .account
+                    .ok_or(anyhow!("No account in response"))?,
+            )),
+            Err(status) if Error::from(status.clone()).is_not_found() => Ok(None),
+            Err(status) => Err(status.into()),
+        }
+    }
+
+    /// [`BaseAccount`] of `address` decoded with `account_decoder`, `None` if it doesn't exist yet.
+    pub async fn base_account(&self, address: impl Into<String>) -> AnyResult<Option<BaseAccount>> {
+        self.account(address)
+            .await?
+            .map(|account| self.account_decoder.decode(&account))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::result_large_err)]
+mod test {
+    use {
+        super::{AccountDecoder, EthAccount},
+        crate::mock::mock_client,
+        cosmos_sdk_proto::cosmos::{
+            auth::v1beta1::{BaseAccount, QueryAccountRequest, QueryAccountResponse},
+            vesting::v1beta1::{BaseVestingAccount, ContinuousVestingAccount},
+        },
+        prost::Message,
+        prost_types::Any,
+        tonic::Status,
+    };
+
+    #[test]
+    fn decode_accounts() {
+        let base = BaseAccount {
+            address: "osmo1...".to_string(),
+            pub_key: None,
+            account_number: 10,
+            sequence: 5,
+        };
+
+        let vesting = ContinuousVestingAccount {
+            base_vesting_account: Some(BaseVestingAccount {
+                base_account: Some(base.clone()),
+                ..Default::default()
+            }),
+            start_time: 0,
+        };
+
+        let decoder = AccountDecoder::default();
+
+        let account = decoder
+            .decode(&Any {
+                type_url: "/cosmos.vesting.v1beta1.ContinuousVestingAccount".to_string(),
+                value: vesting.encode_to_vec(),
+            })
+            .unwrap();
+        assert_eq!(account, base);
+
+        let account = decoder
+            .decode(&Any {
+                type_url: "/ethermint.types.v1.EthAccount".to_string(),
+                value: EthAccount {
+                    base_account: Some(base.clone()),
+                }
+                .encode_to_vec(),
+            })
+            .unwrap();
+        assert_eq!(account, base);
+
+        assert!(decoder
+            .decode(&Any {
+                type_url: "/custom.Account".to_string(),
+                value: vec![],
+            })
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn account_not_found() {
+        let client = mock_client(|path, _, request| {
+            assert_eq!(path, "/cosmos.auth.v1beta1.Query/Account");
+
+            match QueryAccountRequest::decode(request)
+                .unwrap()
+                .address
+                .as_str()
+            {
+                "osmo1existing" => Ok(QueryAccountResponse {
+                    account: Some(Any {
+                        type_url: "/cosmos.auth.v1beta1.BaseAccount".to_string(),
+                        value: BaseAccount {
+                            address: "osmo1existing".to_string(),
+                            pub_key: None,
+                            account_number: 10,
+                            sequence: 5,
+                        }
+                        .encode_to_vec(),
+                    }),
+                }
+                .encode_to_vec()),
+                "osmo1new" => Err(Status::not_found("account osmo1new not found")),
+                _ => Err(Status::internal("key not found")),
+            }
+        })
+        .await;
+
+        let account = client.base_account("osmo1existing").await.unwrap().unwrap();
+        assert_eq!((account.account_number, account.sequence), (10, 5));
+
+        assert!(client.base_account("osmo1new").await.unwrap().is_none());
+
+        // Only `NotFound` means that the account doesn't exist
+        assert!(client.base_account("osmo1other").await.is_err());
+    }
+}
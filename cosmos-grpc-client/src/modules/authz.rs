@@ -0,0 +1,512 @@
+use {
+    crate::{
+        client::GrpcClient,
+        definitions::BroadcastMode,
+        math::IntoProtoCoin,
+        pagination::PaginationOptions,
+        traits::{ProstMsgNameToAny, ProstMsgToAny, SharedAny},
+        tx_options::TxOptions,
+        wallet::Wallet,
+        AnyResult,
+    },
+    cosmos_sdk_proto::cosmos::{
+        authz::v1beta1::{
+            GenericAuthorization, Grant, GrantAuthorization, MsgExec, MsgGrant, MsgRevoke,
+            QueryGranteeGrantsRequest, QueryGranteeGrantsResponse, QueryGranterGrantsRequest,
+            QueryGranterGrantsResponse, QueryGrantsRequest, QueryGrantsResponse,
+        },
+        bank::v1beta1::SendAuthorization,
+        base::v1beta1::Coin as ProtoCoin,
+        tx::v1beta1::BroadcastTxResponse,
+    },
+    cosmrs::tx::Fee,
+    cosmwasm_std::Coin,
+    prost_types::Any,
+    std::time::SystemTime,
+};
+
+// Types without `prost::Name` in `cosmos-sdk-proto` 0.21
+const GENERIC_AUTHORIZATION: &str = "/cosmos.authz.v1beta1.GenericAuthorization";
+const SEND_AUTHORIZATION: &str = "/cosmos.bank.v1beta1.SendAuthorization";
+const MSG_REVOKE: &str = "/cosmos.authz.v1beta1.MsgRevoke";
+
+/// Limit of a [`ContractGrant`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ContractExecutionLimit {
+    /// Max number of executions.
+    MaxCalls(u64),
+    /// Max funds sent to the contract.
+    MaxFunds(Vec<Coin>),
+    /// Max number of executions and funds sent to the contract.
+    Combined { calls: u64, funds: Vec<Coin> },
+}
+
+/// Messages allowed by a [`ContractGrant`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ContractExecutionFilter {
+    AllowAll,
+    /// Only messages with one of these top level keys.
+    AcceptedKeys(Vec<String>),
+    /// Only these raw json messages.
+    AcceptedMessages(Vec<Vec<u8>>),
+}
+
+/// Permission to execute `contract`, used by [`Wallet::grant_contract_execution_authorization`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContractGrant {
+    pub contract: String,
+    pub limit: ContractExecutionLimit,
+    pub filter: ContractExecutionFilter,
+}
+
+/// `cosmwasm.wasm.v1.ContractExecutionAuthorization`, missing in `cosmos-sdk-proto`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct ContractExecutionAuthorization {
+    #[prost(message, repeated, tag = "1")]
+    grants: Vec<ContractGrantProto>,
+}
+
+impl prost::Name for ContractExecutionAuthorization {
+    const NAME: &'static str = "ContractExecutionAuthorization";
+    const PACKAGE: &'static str = "cosmwasm.wasm.v1";
+}
+
+/// `cosmwasm.wasm.v1.ContractGrant`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct ContractGrantProto {
+    #[prost(string, tag = "1")]
+    contract: String,
+    #[prost(message, optional, tag = "2")]
+    limit: Option<Any>,
+    #[prost(message, optional, tag = "3")]
+    filter: Option<Any>,
+}
+
+/// `cosmwasm.wasm.v1.MaxCallsLimit`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct MaxCallsLimit {
+    #[prost(uint64, tag = "1")]
+    remaining: u64,
+}
+
+/// `cosmwasm.wasm.v1.MaxFundsLimit`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct MaxFundsLimit {
+    #[prost(message, repeated, tag = "1")]
+    amounts: Vec<ProtoCoin>,
+}
+
+/// `cosmwasm.wasm.v1.CombinedLimit`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct CombinedLimit {
+    #[prost(uint64, tag = "1")]
+    calls_remaining: u64,
+    #[prost(message, repeated, tag = "2")]
+    amounts: Vec<ProtoCoin>,
+}
+
+/// `cosmwasm.wasm.v1.AcceptedMessageKeysFilter`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct AcceptedMessageKeysFilter {
+    #[prost(string, repeated, tag = "1")]
+    keys: Vec<String>,
+}
+
+/// `cosmwasm.wasm.v1.AcceptedMessagesFilter`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct AcceptedMessagesFilter {
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    messages: Vec<Vec<u8>>,
+}
+
+fn proto_coins(coins: Vec<Coin>) -> Vec<ProtoCoin> {
+    coins
+        .into_iter()
+        .map(IntoProtoCoin::into_proto_coin)
+        .collect()
+}
+
+impl From<ContractGrant> for ContractGrantProto {
+    fn from(grant: ContractGrant) -> Self {
+        let limit = match grant.limit {
+            ContractExecutionLimit::MaxCalls(remaining) => MaxCallsLimit { remaining }
+                .build_any_with_type_url("/cosmwasm.wasm.v1.MaxCallsLimit"),
+            ContractExecutionLimit::MaxFunds(funds) => MaxFundsLimit {
+                amounts: proto_coins(funds),
+            }
+            .build_any_with_type_url("/cosmwasm.wasm.v1.MaxFundsLimit"),
+            ContractExecutionLimit::Combined { calls, funds } => CombinedLimit {
+                calls_remaining: calls,
+                amounts: proto_coins(funds),
+            }
+            .build_any_with_type_url("/cosmwasm.wasm.v1.CombinedLimit"),
+        };
+
+        let filter = match grant.filter {
+            ContractExecutionFilter::AllowAll => Any {
+                type_url: "/cosmwasm.wasm.v1.AllowAllMessagesFilter".to_string(),
+                value: vec![],
+            },
+            ContractExecutionFilter::AcceptedKeys(keys) => AcceptedMessageKeysFilter { keys }
+                .build_any_with_type_url("/cosmwasm.wasm.v1.AcceptedMessageKeysFilter"),
+            ContractExecutionFilter::AcceptedMessages(messages) => {
+                AcceptedMessagesFilter { messages }
+                    .build_any_with_type_url("/cosmwasm.wasm.v1.AcceptedMessagesFilter")
+            }
+        };
+
+        ContractGrantProto {
+            contract: grant.contract,
+            limit: Some(limit),
+            filter: Some(filter),
+        }
+    }
+}
+
+/// Helpers for the `authz` module.
+impl GrpcClient {
+    /// Grants from `granter` to `grantee`, optionally filtered by `msg_type_url`.
+    pub async fn authz_grants(
+        &self,
+        granter: impl Into<String>,
+        grantee: impl Into<String>,
+        msg_type_url: Option<String>,
+    ) -> AnyResult<Vec<Grant>> {
+        self.proto_query_all::<_, QueryGrantsResponse>(
+            QueryGrantsRequest {
+                granter: granter.into(),
+                grantee: grantee.into(),
+                msg_type_url: msg_type_url.unwrap_or_default(),
+                pagination: None,
+            },
+            "/cosmos.authz.v1beta1.Query/Grants",
+            PaginationOptions::default(),
+        )
+        .await
+    }
+
+    /// All the grants given by `granter`.
+    pub async fn authz_granter_grants(
+        &self,
+        granter: impl Into<String>,
+    ) -> AnyResult<Vec<GrantAuthorization>> {
+        self.proto_query_all::<_, QueryGranterGrantsResponse>(
+            QueryGranterGrantsRequest {
+                granter: granter.into(),
+                pagination: None,
+            },
+            "/cosmos.authz.v1beta1.Query/GranterGrants",
+            PaginationOptions::default(),
+        )
+        .await
+    }
+
+    /// All the grants received by `grantee`.
+    pub async fn authz_grantee_grants(
+        &self,
+        grantee: impl Into<String>,
+    ) -> AnyResult<Vec<GrantAuthorization>> {
+        self.proto_query_all::<_, QueryGranteeGrantsResponse>(
+            QueryGranteeGrantsRequest {
+                grantee: grantee.into(),
+                pagination: None,
+            },
+            "/cosmos.authz.v1beta1.Query/GranteeGrants",
+            PaginationOptions::default(),
+        )
+        .await
+    }
+}
+
+/// Helpers to interact with the `authz` module.
+impl Wallet {
+    /// Grant `authorization` to `grantee`, valid until `expiration` (no expiration if `None`).
+    pub async fn grant_authorization(
+        &mut self,
+        grantee: impl Into<String>,
+        authorization: Any,
+        expiration: Option<SystemTime>,
+        broadcast_mode: BroadcastMode,
+    ) -> AnyResult<BroadcastTxResponse> {
+        let msg = MsgGrant {
+            granter: self.account_address.clone(),
+            grantee: grantee.into(),
+            grant: Some(Grant {
+                authorization: Some(authorization),
+                expiration: expiration.map(Into::into),
+            }),
+        };
+
+        self.broadcast_tx(
+            vec![msg.build_any()],
+            None,
+            None,
+            TxOptions::default(),
+            broadcast_mode,
+        )
+        .await
+    }
+
+    /// Allow `grantee` to execute any message of type `msg_type_url`, e.g. `/cosmos.gov.v1.MsgVote`.
+    pub async fn grant_generic_authorization(
+        &mut self,
+        grantee: impl Into<String>,
+        msg_type_url: impl Into<String>,
+        expiration: Option<SystemTime>,
+        broadcast_mode: BroadcastMode,
+    ) -> AnyResult<BroadcastTxResponse> {
+        let authorization = GenericAuthorization {
+            msg: msg_type_url.into(),
+        }
+        .build_any_with_type_url(GENERIC_AUTHORIZATION);
+
+        self.grant_authorization(grantee, authorization, expiration, broadcast_mode)
+            .await
+    }
+
+    /// Allow `grantee` to send up to `spend_limit` on behalf of the wallet.
+    pub async fn grant_send_authorization(
+        &mut self,
+        grantee: impl Into<String>,
+        spend_limit: Vec<Coin>,
+        expiration: Option<SystemTime>,
+        broadcast_mode: BroadcastMode,
+    ) -> AnyResult<BroadcastTxResponse> {
+        let authorization = SendAuthorization {
+            spend_limit: proto_coins(spend_limit),
+        }
+        .build_any_with_type_url(SEND_AUTHORIZATION);
+
+        self.grant_authorization(grantee, authorization, expiration, broadcast_mode)
+            .await
+    }
+
+    /// Allow `grantee` to execute the contracts of `grants` on behalf of the wallet.
+    pub async fn grant_contract_execution_authorization(
+        &mut self,
+        grantee: impl Into<String>,
+        grants: Vec<ContractGrant>,
+        expiration: Option<SystemTime>,
+        broadcast_mode: BroadcastMode,
+    ) -> AnyResult<BroadcastTxResponse> {
+        let authorization = ContractExecutionAuthorization {
+            grants: grants.into_iter().map(Into::into).collect(),
+        }
+        .build_any();
+
+        self.grant_authorization(grantee, authorization, expiration, broadcast_mode)
+            .await
+    }
+
+    /// Revoke the authorization for `msg_type_url` granted to `grantee`.
+    pub async fn revoke_authorization(
+        &mut self,
+        grantee: impl Into<String>,
+        msg_type_url: impl Into<String>,
+        broadcast_mode: BroadcastMode,
+    ) -> AnyResult<BroadcastTxResponse> {
+        let msg = MsgRevoke {
+            granter: self.account_address.clone(),
+            grantee: grantee.into(),
+            msg_type_url: msg_type_url.into(),
+        }
+        .build_any_with_type_url(MSG_REVOKE);
+
+        self.broadcast_tx(vec![msg], None, None, TxOptions::default(), broadcast_mode)
+            .await
+    }
+
+    /// Broadcast `msgs` inside a `MsgExec`, executing them on behalf of their granters.
+    pub async fn broadcast_exec_tx(
+        &mut self,
+        msgs: Vec<impl SharedAny>,
+        fee: Option<Fee>,
+        memo: Option<String>,
+        tx_options: TxOptions,
+        broadcast_mode: BroadcastMode,
+    ) -> AnyResult<BroadcastTxResponse> {
+        let msg = MsgExec {
+            grantee: self.account_address.clone(),
+            msgs: msgs.into_iter().map(SharedAny::into_any).collect(),
+        };
+
+        self.broadcast_tx(vec![msg.build_any()], fee, memo, tx_options, broadcast_mode)
+            .await
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::result_large_err)]
+mod test {
+    use {
+        super::{
+            ContractExecutionAuthorization, ContractExecutionFilter, ContractExecutionLimit,
+            ContractGrant, MaxCallsLimit,
+        },
+        crate::{
+            mock::{decode_tx, mock_wallet, simulate_response},
+            BroadcastMode, ProstMsgNameToAny, TxOptions,
+        },
+        cosmos_sdk_proto::cosmos::{
+            authz::v1beta1::{GenericAuthorization, MsgExec, MsgGrant, MsgRevoke},
+            bank::v1beta1::{MsgSend, SendAuthorization},
+            base::v1beta1::Coin as ProtoCoin,
+            tx::v1beta1::{BroadcastTxRequest, BroadcastTxResponse},
+        },
+        cosmwasm_std::Coin,
+        prost::Message,
+        prost_types::Any,
+        std::sync::{Arc, Mutex},
+        tonic::Status,
+    };
+
+    #[tokio::test]
+    async fn authz_msgs() {
+        // Message of each broadcasted tx
+        let sent = Arc::new(Mutex::new(vec![]));
+
+        let node_sent = sent.clone();
+        let mut wallet = mock_wallet(move |path, _, request| match path {
+            "/cosmos.tx.v1beta1.Service/Simulate" => Ok(simulate_response(100_000)),
+            "/cosmos.tx.v1beta1.Service/BroadcastTx" => {
+                let (mut body, _) =
+                    decode_tx(&BroadcastTxRequest::decode(request).unwrap().tx_bytes);
+                node_sent.lock().unwrap().push(body.messages.remove(0));
+
+                Ok(BroadcastTxResponse::default().encode_to_vec())
+            }
+            _ => Err(Status::unimplemented(path.to_string())),
+        })
+        .await;
+
+        let granter = wallet.account_address.clone();
+
+        wallet
+            .grant_generic_authorization(
+                "osmo1grantee",
+                "/cosmos.gov.v1.MsgVote",
+                None,
+                BroadcastMode::Sync,
+            )
+            .await
+            .unwrap();
+        wallet
+            .grant_send_authorization(
+                "osmo1grantee",
+                vec![Coin::new(10, "uosmo")],
+                None,
+                BroadcastMode::Sync,
+            )
+            .await
+            .unwrap();
+        wallet
+            .grant_contract_execution_authorization(
+                "osmo1grantee",
+                vec![ContractGrant {
+                    contract: "osmo1contract".to_string(),
+                    limit: ContractExecutionLimit::MaxCalls(5),
+                    filter: ContractExecutionFilter::AllowAll,
+                }],
+                None,
+                BroadcastMode::Sync,
+            )
+            .await
+            .unwrap();
+        wallet
+            .revoke_authorization(
+                "osmo1grantee",
+                "/cosmos.gov.v1.MsgVote",
+                BroadcastMode::Sync,
+            )
+            .await
+            .unwrap();
+
+        let send = MsgSend {
+            from_address: "osmo1granter".to_string(),
+            to_address: "osmo1recipient".to_string(),
+            amount: vec![],
+        }
+        .build_any();
+        wallet
+            .broadcast_exec_tx(
+                vec![send.clone()],
+                None,
+                None,
+                TxOptions::default(),
+                BroadcastMode::Sync,
+            )
+            .await
+            .unwrap();
+
+        let sent = sent.lock().unwrap();
+
+        let grant_authorization = |msg: &Any| {
+            assert_eq!(msg.type_url, "/cosmos.authz.v1beta1.MsgGrant");
+            let grant = MsgGrant::decode(msg.value.as_slice()).unwrap();
+            assert_eq!(
+                (grant.granter.as_str(), grant.grantee.as_str()),
+                (granter.as_str(), "osmo1grantee")
+            );
+            grant.grant.unwrap().authorization.unwrap()
+        };
+
+        let authorization = grant_authorization(&sent[0]);
+        assert_eq!(
+            authorization.type_url,
+            "/cosmos.authz.v1beta1.GenericAuthorization"
+        );
+        assert_eq!(
+            GenericAuthorization::decode(authorization.value.as_slice())
+                .unwrap()
+                .msg,
+            "/cosmos.gov.v1.MsgVote"
+        );
+
+        let authorization = grant_authorization(&sent[1]);
+        assert_eq!(
+            authorization.type_url,
+            "/cosmos.bank.v1beta1.SendAuthorization"
+        );
+        assert_eq!(
+            SendAuthorization::decode(authorization.value.as_slice())
+                .unwrap()
+                .spend_limit,
+            vec![ProtoCoin {
+                denom: "uosmo".to_string(),
+                amount: "10".to_string(),
+            }]
+        );
+
+        let authorization = grant_authorization(&sent[2]);
+        assert_eq!(
+            authorization.type_url,
+            "/cosmwasm.wasm.v1.ContractExecutionAuthorization"
+        );
+        let grant = ContractExecutionAuthorization::decode(authorization.value.as_slice())
+            .unwrap()
+            .grants
+            .remove(0);
+        assert_eq!(grant.contract, "osmo1contract");
+        let limit = grant.limit.unwrap();
+        assert_eq!(limit.type_url, "/cosmwasm.wasm.v1.MaxCallsLimit");
+        assert_eq!(
+            MaxCallsLimit::decode(limit.value.as_slice())
+                .unwrap()
+                .remaining,
+            5
+        );
+        assert_eq!(
+            grant.filter.unwrap().type_url,
+            "/cosmwasm.wasm.v1.AllowAllMessagesFilter"
+        );
+
+        assert_eq!(sent[3].type_url, "/cosmos.authz.v1beta1.MsgRevoke");
+        let revoke = MsgRevoke::decode(sent[3].value.as_slice()).unwrap();
+        assert_eq!(revoke.msg_type_url, "/cosmos.gov.v1.MsgVote");
+
+        assert_eq!(sent[4].type_url, "/cosmos.authz.v1beta1.MsgExec");
+        let exec = MsgExec::decode(sent[4].value.as_slice()).unwrap();
+        assert_eq!(exec.grantee, granter);
+        assert_eq!(exec.msgs, vec![send]);
+    }
+}
@@ -0,0 +1,149 @@
+use {
+    crate::{
+        client::GrpcClient, math::TryIntoStdCoin, pagination::PaginationOptions, traits::OkOrAny,
+        AnyResult,
+    },
+    cosmos_sdk_proto::cosmos::bank::v1beta1::{
+        Metadata, QueryAllBalancesRequest, QueryAllBalancesResponse, QueryBalanceRequest,
+        QueryDenomMetadataRequest, QuerySpendableBalancesRequest, QuerySpendableBalancesResponse,
+        QuerySupplyOfRequest,
+    },
+    cosmwasm_std::{Coin, Uint128},
+};
+
+/// Helpers for the `bank` module.
+impl GrpcClient {
+    /// Balance of `address` for `denom`.
+    pub async fn balance(
+        &self,
+        address: impl Into<String>,
+        denom: impl Into<String>,
+    ) -> AnyResult<Uint128> {
+        let balance = self
+            .clients
+            .bank
+            .clone()
+            .balance(QueryBalanceRequest {
+                address: address.into(),
+                denom: denom.into(),
+            })
+            .await?
+            .into_inner()
+            .balance;
+
+        match balance {
+            Some(balance) => Ok(balance.try_into_std_coin()?.amount),
+            None => Ok(Uint128::zero()),
+        }
+    }
+
+    /// All the balances of `address`.
+    pub async fn all_balances(&self, address: impl Into<String>) -> AnyResult<Vec<Coin>> {
+        self.proto_query_all::<_, QueryAllBalancesResponse>(
+            QueryAllBalancesRequest {
+                address: address.into(),
+                pagination: None,
+            },
+            "/cosmos.bank.v1beta1.Query/AllBalances",
+            PaginationOptions::default(),
+        )
+        .await?
+        .into_iter()
+        .map(TryIntoStdCoin::try_into_std_coin)
+        .collect()
+    }
+
+    /// Balances of `address` not locked by vesting or delegations.
+    pub async fn spendable_balances(&self, address: impl Into<String>) -> AnyResult<Vec<Coin>> {
+        self.proto_query_all::<_, QuerySpendableBalancesResponse>(
+            QuerySpendableBalancesRequest {
+                address: address.into(),
+                pagination: None,
+            },
+            "/cosmos.bank.v1beta1.Query/SpendableBalances",
+            PaginationOptions::default(),
+        )
+        .await?
+        .into_iter()
+        .map(TryIntoStdCoin::try_into_std_coin)
+        .collect()
+    }
+
+    /// Total supply of `denom`.
+    pub async fn supply_of(&self, denom: impl Into<String>) -> AnyResult<Uint128> {
+        Ok(self
+            .clients
+            .bank
+            .clone()
+            .supply_of(QuerySupplyOfRequest {
+                denom: denom.into(),
+            })
+            .await?
+            .into_inner()
+            .amount
+            .ok_or_any("No amount in supply response")?
+            .try_into_std_coin()?
+            .amount)
+    }
+
+    /// Metadata of `denom`.
+    pub async fn denom_metadata(&self, denom: impl Into<String>) -> AnyResult<Metadata> {
+        self.clients
+            .bank
+            .clone()
+            .denom_metadata(QueryDenomMetadataRequest {
+                denom: denom.into(),
+            })
+            .await?
+            .into_inner()
+            .metadata
+            .ok_or_any("No metadata in response")
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::result_large_err)]
+mod test {
+    use {
+        crate::mock::mock_client,
+        cosmos_sdk_proto::cosmos::{
+            bank::v1beta1::{QueryAllBalancesRequest, QueryAllBalancesResponse},
+            base::{query::v1beta1::PageResponse, v1beta1::Coin as ProtoCoin},
+        },
+        cosmwasm_std::Coin,
+        prost::Message,
+        tonic::Status,
+    };
+
+    #[tokio::test]
+    async fn all_balances() {
+        let client = mock_client(|path, _, request| {
+            assert_eq!(path, "/cosmos.bank.v1beta1.Query/AllBalances");
+
+            let request = QueryAllBalancesRequest::decode(request).unwrap();
+            assert_eq!(request.address, "osmo1...");
+
+            // The second page is requested with the key returned by the first one
+            let (denom, next_key) = match request.pagination.unwrap().key.as_slice() {
+                b"" => ("uatom", b"uosmo".to_vec()),
+                b"uosmo" => ("uosmo", vec![]),
+                key => return Err(Status::invalid_argument(format!("unknown key {key:?}"))),
+            };
+
+            Ok(QueryAllBalancesResponse {
+                balances: vec![ProtoCoin {
+                    denom: denom.to_string(),
+                    amount: "10".to_string(),
+                }],
+                pagination: Some(PageResponse { next_key, total: 0 }),
+            }
+            .encode_to_vec())
+        })
+        .await;
+
+        assert_eq!(
+            client.all_balances("osmo1...").await.unwrap(),
+            vec![Coin::new(10, "uatom"), Coin::new(10, "uosmo")]
+        );
+    }
+}
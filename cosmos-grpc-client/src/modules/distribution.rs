@@ -0,0 +1,155 @@
+use {
+    crate::{client::GrpcClient, math::TryIntoStdDecCoin, AnyResult},
+    cosmos_sdk_proto::cosmos::distribution::v1beta1::{
+        QueryDelegationRewardsRequest, QueryDelegationTotalRewardsRequest,
+        QueryValidatorCommissionRequest,
+    },
+    cosmwasm_std::DecCoin,
+};
+
+/// Pending rewards of a delegator.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DelegatorRewards {
+    /// Rewards for each validator, as `(validator_address, rewards)`.
+    pub rewards: Vec<(String, Vec<DecCoin>)>,
+    pub total: Vec<DecCoin>,
+}
+
+/// Helpers for the `distribution` module.
+impl GrpcClient {
+    /// Pending rewards of `delegator_address`, for each validator and in total.
+    pub async fn delegation_total_rewards(
+        &self,
+        delegator_address: impl Into<String>,
+    ) -> AnyResult<DelegatorRewards> {
+        let res = self
+            .clients
+            .distribution
+            .clone()
+            .delegation_total_rewards(QueryDelegationTotalRewardsRequest {
+                delegator_address: delegator_address.into(),
+            })
+            .await?
+            .into_inner();
+
+        Ok(DelegatorRewards {
+            rewards: res
+                .rewards
+                .into_iter()
+                .map(|reward| {
+                    Ok((
+                        reward.validator_address,
+                        reward
+                            .reward
+                            .into_iter()
+                            .map(TryIntoStdDecCoin::try_into_std_dec_coin)
+                            .collect::<AnyResult<_>>()?,
+                    ))
+                })
+                .collect::<AnyResult<_>>()?,
+            total: res
+                .total
+                .into_iter()
+                .map(TryIntoStdDecCoin::try_into_std_dec_coin)
+                .collect::<AnyResult<_>>()?,
+        })
+    }
+
+    /// Pending rewards of `delegator_address` from `validator_address`.
+    pub async fn delegation_rewards(
+        &self,
+        delegator_address: impl Into<String>,
+        validator_address: impl Into<String>,
+    ) -> AnyResult<Vec<DecCoin>> {
+        self.clients
+            .distribution
+            .clone()
+            .delegation_rewards(QueryDelegationRewardsRequest {
+                delegator_address: delegator_address.into(),
+                validator_address: validator_address.into(),
+            })
+            .await?
+            .into_inner()
+            .rewards
+            .into_iter()
+            .map(TryIntoStdDecCoin::try_into_std_dec_coin)
+            .collect()
+    }
+
+    /// Accumulated commission of `validator_address`.
+    pub async fn validator_commission(
+        &self,
+        validator_address: impl Into<String>,
+    ) -> AnyResult<Vec<DecCoin>> {
+        self.clients
+            .distribution
+            .clone()
+            .validator_commission(QueryValidatorCommissionRequest {
+                validator_address: validator_address.into(),
+            })
+            .await?
+            .into_inner()
+            .commission
+            .map(|commission| commission.commission)
+            .unwrap_or_default()
+            .into_iter()
+            .map(TryIntoStdDecCoin::try_into_std_dec_coin)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::result_large_err)]
+mod test {
+    use {
+        super::DelegatorRewards,
+        crate::mock::mock_client,
+        cosmos_sdk_proto::cosmos::{
+            base::v1beta1::DecCoin as ProtoDecCoin,
+            distribution::v1beta1::{
+                DelegationDelegatorReward, QueryDelegationTotalRewardsResponse,
+            },
+        },
+        cosmwasm_std::{DecCoin, Decimal256},
+        prost::Message,
+        std::str::FromStr,
+        tonic::Status,
+    };
+
+    #[tokio::test]
+    async fn delegation_rewards() {
+        let client = mock_client(|path, _, _| match path {
+            "/cosmos.distribution.v1beta1.Query/DelegationTotalRewards" => {
+                Ok(QueryDelegationTotalRewardsResponse {
+                    rewards: vec![DelegationDelegatorReward {
+                        validator_address: "osmovaloper1...".to_string(),
+                        reward: vec![ProtoDecCoin {
+                            denom: "uosmo".to_string(),
+                            amount: "1234567890123456789".to_string(),
+                        }],
+                    }],
+                    total: vec![ProtoDecCoin {
+                        denom: "uosmo".to_string(),
+                        amount: "1234567890123456789".to_string(),
+                    }],
+                }
+                .encode_to_vec())
+            }
+            _ => Err(Status::unimplemented(path.to_string())),
+        })
+        .await;
+
+        let reward = DecCoin::new(
+            Decimal256::from_str("1.234567890123456789").unwrap(),
+            "uosmo",
+        );
+
+        assert_eq!(
+            client.delegation_total_rewards("osmo1...").await.unwrap(),
+            DelegatorRewards {
+                rewards: vec![("osmovaloper1...".to_string(), vec![reward.clone()])],
+                total: vec![reward],
+            }
+        );
+    }
+}
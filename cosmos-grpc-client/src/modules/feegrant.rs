@@ -0,0 +1,322 @@
+use {
+    crate::{
+        client::GrpcClient, definitions::BroadcastMode, math::IntoProtoCoin,
+        pagination::PaginationOptions, traits::ProstMsgNameToAny, tx_options::TxOptions,
+        wallet::Wallet, AnyResult,
+    },
+    cosmos_sdk_proto::cosmos::{
+        feegrant::v1beta1::{
+            BasicAllowance, Grant, MsgGrantAllowance, MsgRevokeAllowance, PeriodicAllowance,
+            QueryAllowanceRequest, QueryAllowancesByGranterRequest,
+            QueryAllowancesByGranterResponse, QueryAllowancesRequest, QueryAllowancesResponse,
+        },
+        tx::v1beta1::BroadcastTxResponse,
+    },
+    cosmwasm_std::Coin,
+    std::time::{Duration, SystemTime},
+    tonic::Code,
+};
+
+/// Helpers for the `feegrant` module.
+impl GrpcClient {
+    /// Allowance granted by `granter` to `grantee`, if any.
+    pub async fn fee_allowance(
+        &self,
+        granter: impl Into<String>,
+        grantee: impl Into<String>,
+    ) -> AnyResult<Option<Grant>> {
+        let response = self
+            .clients
+            .fee_grant
+            .clone()
+            .allowance(QueryAllowanceRequest {
+                granter: granter.into(),
+                grantee: grantee.into(),
+            })
+            .await;
+
+        match response {
+            Ok(response) => Ok(response.into_inner().allowance),
+            Err(status) if status.code() == Code::NotFound => Ok(None),
+            Err(status) if status.message().contains("not found") => Ok(None),
+            Err(status) => Err(status.into()),
+        }
+    }
+
+    /// All the allowances granted to `grantee`.
+    pub async fn fee_allowances(&self, grantee: impl Into<String>) -> AnyResult<Vec<Grant>> {
+        self.proto_query_all::<_, QueryAllowancesResponse>(
+            QueryAllowancesRequest {
+                grantee: grantee.into(),
+                pagination: None,
+            },
+            "/cosmos.feegrant.v1beta1.Query/Allowances",
+            PaginationOptions::default(),
+        )
+        .await
+    }
+
+    /// All the allowances granted by `granter`.
+    pub async fn fee_allowances_by_granter(
+        &self,
+        granter: impl Into<String>,
+    ) -> AnyResult<Vec<Grant>> {
+        self.proto_query_all::<_, QueryAllowancesByGranterResponse>(
+            QueryAllowancesByGranterRequest {
+                granter: granter.into(),
+                pagination: None,
+            },
+            "/cosmos.feegrant.v1beta1.Query/AllowancesByGranter",
+            PaginationOptions::default(),
+        )
+        .await
+    }
+}
+
+/// Helpers to interact with the `feegrant` module.
+impl Wallet {
+    /// Grant to `grantee` an allowance to pay fees up to `spend_limit` (no limit if empty) until `expiration`.
+    pub async fn grant_basic_allowance(
+        &mut self,
+        grantee: impl Into<String>,
+        spend_limit: Vec<Coin>,
+        expiration: Option<SystemTime>,
+        broadcast_mode: BroadcastMode,
+    ) -> AnyResult<BroadcastTxResponse> {
+        let allowance = basic_allowance(spend_limit, expiration);
+
+        self.grant_allowance(grantee, allowance.build_any(), broadcast_mode)
+            .await
+    }
+
+    /// Grant to `grantee` an allowance to pay fees up to `period_spend_limit` every `period`,
+    /// and up to `spend_limit` (no limit if empty) in total, until `expiration`.
+    pub async fn grant_periodic_allowance(
+        &mut self,
+        grantee: impl Into<String>,
+        spend_limit: Vec<Coin>,
+        expiration: Option<SystemTime>,
+        period: Duration,
+        period_spend_limit: Vec<Coin>,
+        broadcast_mode: BroadcastMode,
+    ) -> AnyResult<BroadcastTxResponse> {
+        let period_spend_limit = period_spend_limit
+            .into_iter()
+            .map(IntoProtoCoin::into_proto_coin)
+            .collect::<Vec<_>>();
+
+        let allowance = PeriodicAllowance {
+            basic: Some(basic_allowance(spend_limit, expiration)),
+            period: Some(period.try_into()?),
+            period_can_spend: period_spend_limit.clone(),
+            period_spend_limit,
+            period_reset: None,
+        };
+
+        self.grant_allowance(grantee, allowance.build_any(), broadcast_mode)
+            .await
+    }
+
+    /// Revoke the allowance granted to `grantee`.
+    pub async fn revoke_allowance(
+        &mut self,
+        grantee: impl Into<String>,
+        broadcast_mode: BroadcastMode,
+    ) -> AnyResult<BroadcastTxResponse> {
+        let msg = MsgRevokeAllowance {
+            granter: self.account_address.clone(),
+            grantee: grantee.into(),
+        };
+
+        self.broadcast_tx(
+            vec![msg.build_any()],
+            None,
+            None,
+            TxOptions::default(),
+            broadcast_mode,
+        )
+        .await
+    }
+
+    async fn grant_allowance(
+        &mut self,
+        grantee: impl Into<String>,
+        allowance: prost_types::Any,
+        broadcast_mode: BroadcastMode,
+    ) -> AnyResult<BroadcastTxResponse> {
+        let msg = MsgGrantAllowance {
+            granter: self.account_address.clone(),
+            grantee: grantee.into(),
+            allowance: Some(allowance),
+        };
+
+        self.broadcast_tx(
+            vec![msg.build_any()],
+            None,
+            None,
+            TxOptions::default(),
+            broadcast_mode,
+        )
+        .await
+    }
+}
+
+fn basic_allowance(spend_limit: Vec<Coin>, expiration: Option<SystemTime>) -> BasicAllowance {
+    BasicAllowance {
+        spend_limit: spend_limit
+            .into_iter()
+            .map(IntoProtoCoin::into_proto_coin)
+            .collect(),
+        expiration: expiration.map(Into::into),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::result_large_err)]
+mod test {
+    use {
+        crate::{
+            mock::{decode_tx, mock_wallet, simulate_response},
+            BroadcastMode,
+        },
+        cosmos_sdk_proto::cosmos::{
+            base::v1beta1::Coin as ProtoCoin,
+            feegrant::v1beta1::{
+                BasicAllowance, Grant, MsgGrantAllowance, PeriodicAllowance, QueryAllowanceRequest,
+                QueryAllowanceResponse, QueryAllowancesResponse,
+            },
+            tx::v1beta1::{BroadcastTxRequest, BroadcastTxResponse},
+        },
+        cosmwasm_std::Coin,
+        prost::{Message, Name},
+        prost_types::Any,
+        std::{
+            sync::{Arc, Mutex},
+            time::Duration,
+        },
+        tonic::Status,
+    };
+
+    fn uosmo(amount: &str) -> Vec<ProtoCoin> {
+        vec![ProtoCoin {
+            denom: "uosmo".to_string(),
+            amount: amount.to_string(),
+        }]
+    }
+
+    #[tokio::test]
+    async fn allowances() {
+        let allowance = Any {
+            type_url: BasicAllowance::type_url(),
+            value: BasicAllowance {
+                spend_limit: uosmo("1000"),
+                expiration: None,
+            }
+            .encode_to_vec(),
+        };
+
+        let grant = Grant {
+            granter: "osmo1granter".to_string(),
+            grantee: "osmo1grantee".to_string(),
+            allowance: Some(allowance),
+        };
+
+        // Message of each broadcasted tx
+        let sent = Arc::new(Mutex::new(vec![]));
+
+        let (node_grant, node_sent) = (grant.clone(), sent.clone());
+        let mut wallet = mock_wallet(move |path, _, request| match path {
+            "/cosmos.feegrant.v1beta1.Query/Allowance" => {
+                match QueryAllowanceRequest::decode(request)
+                    .unwrap()
+                    .grantee
+                    .as_str()
+                {
+                    "osmo1grantee" => Ok(QueryAllowanceResponse {
+                        allowance: Some(node_grant.clone()),
+                    }
+                    .encode_to_vec()),
+                    // Returned by the sdk with an `Unknown` code
+                    grantee => Err(Status::unknown(format!(
+                        "fee-grant not found for {grantee}"
+                    ))),
+                }
+            }
+            "/cosmos.feegrant.v1beta1.Query/Allowances" => Ok(QueryAllowancesResponse {
+                allowances: vec![node_grant.clone()],
+                pagination: None,
+            }
+            .encode_to_vec()),
+            "/cosmos.tx.v1beta1.Service/Simulate" => Ok(simulate_response(100_000)),
+            "/cosmos.tx.v1beta1.Service/BroadcastTx" => {
+                let (mut body, _) =
+                    decode_tx(&BroadcastTxRequest::decode(request).unwrap().tx_bytes);
+                node_sent.lock().unwrap().push(body.messages.remove(0));
+
+                Ok(BroadcastTxResponse::default().encode_to_vec())
+            }
+            _ => Err(Status::unimplemented(path.to_string())),
+        })
+        .await;
+
+        let client = wallet.client.clone();
+
+        let found = client
+            .fee_allowance("osmo1granter", "osmo1grantee")
+            .await
+            .unwrap()
+            .unwrap();
+        let allowance = found.allowance.unwrap();
+        assert_eq!(
+            allowance.type_url,
+            "/cosmos.feegrant.v1beta1.BasicAllowance"
+        );
+        assert_eq!(
+            BasicAllowance::decode(allowance.value.as_slice())
+                .unwrap()
+                .spend_limit,
+            uosmo("1000")
+        );
+
+        assert!(client
+            .fee_allowance("osmo1granter", "osmo1other")
+            .await
+            .unwrap()
+            .is_none());
+
+        assert_eq!(
+            client.fee_allowances("osmo1grantee").await.unwrap(),
+            vec![grant]
+        );
+
+        wallet
+            .grant_periodic_allowance(
+                "osmo1grantee",
+                vec![Coin::new(1000, "uosmo")],
+                None,
+                Duration::from_secs(3600),
+                vec![Coin::new(100, "uosmo")],
+                BroadcastMode::Sync,
+            )
+            .await
+            .unwrap();
+
+        let msg = sent.lock().unwrap().remove(0);
+        assert_eq!(msg.type_url, "/cosmos.feegrant.v1beta1.MsgGrantAllowance");
+
+        let allowance = MsgGrantAllowance::decode(msg.value.as_slice())
+            .unwrap()
+            .allowance
+            .unwrap();
+        assert_eq!(
+            allowance.type_url,
+            "/cosmos.feegrant.v1beta1.PeriodicAllowance"
+        );
+
+        let allowance = PeriodicAllowance::decode(allowance.value.as_slice()).unwrap();
+        assert_eq!(allowance.basic.unwrap().spend_limit, uosmo("1000"));
+        assert_eq!(allowance.period.unwrap().seconds, 3600);
+        assert_eq!(allowance.period_spend_limit, uosmo("100"));
+        assert_eq!(allowance.period_can_spend, uosmo("100"));
+    }
+}
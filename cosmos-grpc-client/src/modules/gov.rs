@@ -0,0 +1,351 @@
+use {
+    crate::{
+        client::GrpcClient,
+        definitions::BroadcastMode,
+        math::IntoProtoCoin,
+        pagination::PaginationOptions,
+        traits::{OkOrAny, ProstMsgNameToAny, SharedAny},
+        tx_options::TxOptions,
+        wallet::Wallet,
+        AnyResult,
+    },
+    cosmos_sdk_proto::cosmos::{
+        base::v1beta1::Coin as ProtoCoin,
+        gov::v1::{
+            MsgDeposit, MsgVote, MsgVoteWeighted, Proposal, ProposalStatus, QueryParamsRequest,
+            QueryParamsResponse, QueryProposalRequest, QueryProposalsRequest,
+            QueryProposalsResponse, QueryTallyResultRequest, QueryVotesRequest, QueryVotesResponse,
+            Vote, VoteOption, WeightedVoteOption,
+        },
+        tx::v1beta1::BroadcastTxResponse,
+    },
+    cosmwasm_std::{Coin, Decimal, Uint128},
+    std::str::FromStr,
+};
+
+/// `cosmos.gov.v1.MsgSubmitProposal`, including the fields added by sdk 0.47 (`title`, `summary`) and 0.50 (`expedited`).
+///
+/// Empty fields are not encoded, so the message is still accepted by sdk 0.46 chains.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GovV1MsgSubmitProposal {
+    #[prost(message, repeated, tag = "1")]
+    pub messages: Vec<prost_types::Any>,
+    #[prost(message, repeated, tag = "2")]
+    pub initial_deposit: Vec<ProtoCoin>,
+    #[prost(string, tag = "3")]
+    pub proposer: String,
+    #[prost(string, tag = "4")]
+    pub metadata: String,
+    #[prost(string, tag = "5")]
+    pub title: String,
+    #[prost(string, tag = "6")]
+    pub summary: String,
+    #[prost(bool, tag = "7")]
+    pub expedited: bool,
+}
+
+impl prost::Name for GovV1MsgSubmitProposal {
+    const NAME: &'static str = "MsgSubmitProposal";
+    const PACKAGE: &'static str = "cosmos.gov.v1";
+}
+
+/// Decoded tally of a proposal.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Tally {
+    pub yes: Uint128,
+    pub abstain: Uint128,
+    pub no: Uint128,
+    pub no_with_veto: Uint128,
+}
+
+/// Helpers for the `gov` module (`v1`).
+impl GrpcClient {
+    /// List all the proposals, optionally filtered by `status`.
+    pub async fn proposals(&self, status: Option<ProposalStatus>) -> AnyResult<Vec<Proposal>> {
+        self.proto_query_all::<_, QueryProposalsResponse>(
+            QueryProposalsRequest {
+                proposal_status: status.unwrap_or(ProposalStatus::Unspecified) as i32,
+                voter: "".to_string(),
+                depositor: "".to_string(),
+                pagination: None,
+            },
+            "/cosmos.gov.v1.Query/Proposals",
+            PaginationOptions::default(),
+        )
+        .await
+    }
+
+    pub async fn proposal(&self, proposal_id: u64) -> AnyResult<Proposal> {
+        self.clients
+            .gov_v1
+            .clone()
+            .proposal(QueryProposalRequest { proposal_id })
+            .await?
+            .into_inner()
+            .proposal
+            .ok_or_any("No proposal in response")
+    }
+
+    /// Current tally of a proposal in voting period, or the final one for ended proposals.
+    pub async fn proposal_tally(&self, proposal_id: u64) -> AnyResult<Tally> {
+        let tally = self
+            .clients
+            .gov_v1
+            .clone()
+            .tally_result(QueryTallyResultRequest { proposal_id })
+            .await?
+            .into_inner()
+            .tally
+            .unwrap_or_default();
+
+        let parse = |value: &str| -> AnyResult<Uint128> {
+            if value.is_empty() {
+                Ok(Uint128::zero())
+            } else {
+                Ok(Uint128::from_str(value)?)
+            }
+        };
+
+        Ok(Tally {
+            yes: parse(&tally.yes_count)?,
+            abstain: parse(&tally.abstain_count)?,
+            no: parse(&tally.no_count)?,
+            no_with_veto: parse(&tally.no_with_veto_count)?,
+        })
+    }
+
+    /// All the votes of a proposal. Votes are pruned by the chain once the proposal ends.
+    pub async fn proposal_votes(&self, proposal_id: u64) -> AnyResult<Vec<Vote>> {
+        self.proto_query_all::<_, QueryVotesResponse>(
+            QueryVotesRequest {
+                proposal_id,
+                pagination: None,
+            },
+            "/cosmos.gov.v1.Query/Votes",
+            PaginationOptions::default(),
+        )
+        .await
+    }
+
+    /// Gov params. `params_type` is one of `voting`, `deposit` and `tallying`.
+    pub async fn gov_params(
+        &self,
+        params_type: impl Into<String>,
+    ) -> AnyResult<QueryParamsResponse> {
+        Ok(self
+            .clients
+            .gov_v1
+            .clone()
+            .params(QueryParamsRequest {
+                params_type: params_type.into(),
+            })
+            .await?
+            .into_inner())
+    }
+}
+
+/// Helpers to interact with the `gov` module (`v1`).
+impl Wallet {
+    /// Submit a proposal executing `messages` if it passes.
+    ///
+    /// `title` and `summary` are supported from sdk 0.47, leave them empty for older chains.
+    pub async fn submit_proposal(
+        &mut self,
+        messages: Vec<impl SharedAny>,
+        initial_deposit: Vec<Coin>,
+        title: impl Into<String>,
+        summary: impl Into<String>,
+        metadata: impl Into<String>,
+        broadcast_mode: BroadcastMode,
+    ) -> AnyResult<BroadcastTxResponse> {
+        let msg = GovV1MsgSubmitProposal {
+            messages: messages.into_iter().map(|msg| msg.into_any()).collect(),
+            initial_deposit: initial_deposit
+                .into_iter()
+                .map(IntoProtoCoin::into_proto_coin)
+                .collect(),
+            proposer: self.account_address.clone(),
+            metadata: metadata.into(),
+            title: title.into(),
+            summary: summary.into(),
+            expedited: false,
+        };
+
+        self.broadcast_tx(
+            vec![msg.build_any()],
+            None,
+            None,
+            TxOptions::default(),
+            broadcast_mode,
+        )
+        .await
+    }
+
+    pub async fn deposit(
+        &mut self,
+        proposal_id: u64,
+        amount: Vec<Coin>,
+        broadcast_mode: BroadcastMode,
+    ) -> AnyResult<BroadcastTxResponse> {
+        let msg = MsgDeposit {
+            proposal_id,
+            depositor: self.account_address.clone(),
+            amount: amount
+                .into_iter()
+                .map(IntoProtoCoin::into_proto_coin)
+                .collect(),
+        };
+
+        self.broadcast_tx(
+            vec![msg.build_any()],
+            None,
+            None,
+            TxOptions::default(),
+            broadcast_mode,
+        )
+        .await
+    }
+
+    pub async fn vote(
+        &mut self,
+        proposal_id: u64,
+        option: VoteOption,
+        broadcast_mode: BroadcastMode,
+    ) -> AnyResult<BroadcastTxResponse> {
+        let msg = MsgVote {
+            proposal_id,
+            voter: self.account_address.clone(),
+            option: option as i32,
+            metadata: "".to_string(),
+        };
+
+        self.broadcast_tx(
+            vec![msg.build_any()],
+            None,
+            None,
+            TxOptions::default(),
+            broadcast_mode,
+        )
+        .await
+    }
+
+    /// Split the voting power over more options. The weights must sum up to 1.
+    pub async fn vote_weighted(
+        &mut self,
+        proposal_id: u64,
+        options: Vec<(VoteOption, Decimal)>,
+        broadcast_mode: BroadcastMode,
+    ) -> AnyResult<BroadcastTxResponse> {
+        let msg = MsgVoteWeighted {
+            proposal_id,
+            voter: self.account_address.clone(),
+            options: options
+                .into_iter()
+                .map(|(option, weight)| WeightedVoteOption {
+                    option: option as i32,
+                    weight: weight.to_string(),
+                })
+                .collect(),
+            metadata: "".to_string(),
+        };
+
+        self.broadcast_tx(
+            vec![msg.build_any()],
+            None,
+            None,
+            TxOptions::default(),
+            broadcast_mode,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::result_large_err)]
+mod test {
+    use {
+        super::Tally,
+        crate::mock::mock_client,
+        cosmos_sdk_proto::cosmos::gov::v1::{
+            Proposal, ProposalStatus, QueryProposalsRequest, QueryProposalsResponse,
+            QueryTallyResultResponse, QueryVotesRequest, QueryVotesResponse, TallyResult, Vote,
+            VoteOption, WeightedVoteOption,
+        },
+        cosmwasm_std::Uint128,
+        prost::Message,
+        tonic::Status,
+    };
+
+    #[tokio::test]
+    async fn proposals_and_votes() {
+        let client = mock_client(|path, _, request| match path {
+            "/cosmos.gov.v1.Query/Proposals" => {
+                let request = QueryProposalsRequest::decode(request).unwrap();
+                assert_eq!(request.proposal_status, ProposalStatus::VotingPeriod as i32);
+
+                Ok(QueryProposalsResponse {
+                    proposals: vec![Proposal {
+                        id: 7,
+                        status: ProposalStatus::VotingPeriod as i32,
+                        metadata: "ipfs://proposal".to_string(),
+                        ..Default::default()
+                    }],
+                    pagination: None,
+                }
+                .encode_to_vec())
+            }
+            "/cosmos.gov.v1.Query/Votes" => {
+                assert_eq!(QueryVotesRequest::decode(request).unwrap().proposal_id, 7);
+
+                Ok(QueryVotesResponse {
+                    votes: vec![Vote {
+                        proposal_id: 7,
+                        voter: "osmo1voter".to_string(),
+                        options: vec![WeightedVoteOption {
+                            option: VoteOption::Yes as i32,
+                            weight: "1.000000000000000000".to_string(),
+                        }],
+                        ..Default::default()
+                    }],
+                    pagination: None,
+                }
+                .encode_to_vec())
+            }
+            "/cosmos.gov.v1.Query/TallyResult" => Ok(QueryTallyResultResponse {
+                tally: Some(TallyResult {
+                    yes_count: "1000".to_string(),
+                    abstain_count: "".to_string(),
+                    no_count: "10".to_string(),
+                    no_with_veto_count: "0".to_string(),
+                }),
+            }
+            .encode_to_vec()),
+            _ => Err(Status::unimplemented(path.to_string())),
+        })
+        .await;
+
+        let proposals = client
+            .proposals(Some(ProposalStatus::VotingPeriod))
+            .await
+            .unwrap();
+        assert_eq!(proposals.len(), 1);
+        assert_eq!(
+            (proposals[0].id, proposals[0].metadata.as_str()),
+            (7, "ipfs://proposal")
+        );
+
+        let votes = client.proposal_votes(7).await.unwrap();
+        assert_eq!(votes[0].voter, "osmo1voter");
+        assert_eq!(votes[0].options[0].option, VoteOption::Yes as i32);
+
+        assert_eq!(
+            client.proposal_tally(7).await.unwrap(),
+            Tally {
+                yes: Uint128::new(1000),
+                abstain: Uint128::zero(),
+                no: Uint128::new(10),
+                no_with_veto: Uint128::zero(),
+            }
+        );
+    }
+}
@@ -0,0 +1,7 @@
+pub mod auth;
+pub mod authz;
+pub mod bank;
+pub mod distribution;
+pub mod feegrant;
+pub mod gov;
+pub mod staking;
@@ -0,0 +1,329 @@
+use {
+    crate::{
+        client::GrpcClient,
+        math::{sdk_dec_to_decimal, sdk_dec_to_decimal256, TryIntoStdCoin},
+        pagination::PaginationOptions,
+        traits::OkOrAny,
+        AnyResult,
+    },
+    cosmos_sdk_proto::cosmos::staking::v1beta1::{
+        BondStatus, DelegationResponse, QueryDelegatorDelegationsRequest,
+        QueryDelegatorDelegationsResponse, QueryDelegatorUnbondingDelegationsRequest,
+        QueryDelegatorUnbondingDelegationsResponse, QueryRedelegationsRequest,
+        QueryRedelegationsResponse, QueryValidatorRequest, QueryValidatorsRequest,
+        QueryValidatorsResponse, RedelegationResponse, UnbondingDelegation, Validator,
+    },
+    cosmwasm_std::{Coin, Decimal, Decimal256, Uint128},
+    prost_types::Timestamp,
+    std::str::FromStr,
+};
+
+/// Decoded [`Validator`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidatorInfo {
+    pub operator_address: String,
+    pub moniker: String,
+    pub website: String,
+    pub status: BondStatus,
+    pub jailed: bool,
+    pub tokens: Uint128,
+    pub delegator_shares: Decimal256,
+    pub commission_rate: Decimal,
+    pub commission_max_rate: Decimal,
+    pub commission_max_change_rate: Decimal,
+    pub min_self_delegation: Uint128,
+    pub unbonding_height: i64,
+    pub unbonding_time: Option<Timestamp>,
+}
+
+impl TryFrom<Validator> for ValidatorInfo {
+    type Error = anyhow::Error;
+
+    fn try_from(validator: Validator) -> AnyResult<Self> {
+        let description = validator.description.unwrap_or_default();
+        let rates = validator
+            .commission
+            .and_then(|commission| commission.commission_rates)
+            .unwrap_or_default();
+
+        Ok(ValidatorInfo {
+            operator_address: validator.operator_address,
+            moniker: description.moniker,
+            website: description.website,
+            status: BondStatus::try_from(validator.status)?,
+            jailed: validator.jailed,
+            tokens: Uint128::from_str(&validator.tokens)?,
+            delegator_shares: sdk_dec_to_decimal256(&validator.delegator_shares)?,
+            commission_rate: sdk_dec_to_decimal(&rates.rate)?,
+            commission_max_rate: sdk_dec_to_decimal(&rates.max_rate)?,
+            commission_max_change_rate: sdk_dec_to_decimal(&rates.max_change_rate)?,
+            min_self_delegation: Uint128::from_str(&validator.min_self_delegation)?,
+            unbonding_height: validator.unbonding_height,
+            unbonding_time: validator.unbonding_time,
+        })
+    }
+}
+
+/// Decoded [`DelegationResponse`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DelegationInfo {
+    pub delegator_address: String,
+    pub validator_address: String,
+    pub shares: Decimal256,
+    pub balance: Coin,
+}
+
+impl TryFrom<DelegationResponse> for DelegationInfo {
+    type Error = anyhow::Error;
+
+    fn try_from(response: DelegationResponse) -> AnyResult<Self> {
+        let delegation = response.delegation.ok_or_any("No delegation in response")?;
+
+        Ok(DelegationInfo {
+            delegator_address: delegation.delegator_address,
+            validator_address: delegation.validator_address,
+            shares: sdk_dec_to_decimal256(&delegation.shares)?,
+            balance: response
+                .balance
+                .ok_or_any("No balance in response")?
+                .try_into_std_coin()?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnbondingEntryInfo {
+    pub creation_height: i64,
+    pub completion_time: Option<Timestamp>,
+    pub initial_balance: Uint128,
+    pub balance: Uint128,
+}
+
+/// Decoded [`UnbondingDelegation`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnbondingInfo {
+    pub delegator_address: String,
+    pub validator_address: String,
+    pub entries: Vec<UnbondingEntryInfo>,
+}
+
+impl TryFrom<UnbondingDelegation> for UnbondingInfo {
+    type Error = anyhow::Error;
+
+    fn try_from(unbonding: UnbondingDelegation) -> AnyResult<Self> {
+        Ok(UnbondingInfo {
+            delegator_address: unbonding.delegator_address,
+            validator_address: unbonding.validator_address,
+            entries: unbonding
+                .entries
+                .into_iter()
+                .map(|entry| {
+                    Ok(UnbondingEntryInfo {
+                        creation_height: entry.creation_height,
+                        completion_time: entry.completion_time,
+                        initial_balance: Uint128::from_str(&entry.initial_balance)?,
+                        balance: Uint128::from_str(&entry.balance)?,
+                    })
+                })
+                .collect::<AnyResult<_>>()?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RedelegationEntryInfo {
+    pub creation_height: i64,
+    pub completion_time: Option<Timestamp>,
+    pub initial_balance: Uint128,
+    pub shares_dst: Decimal256,
+    pub balance: Uint128,
+}
+
+/// Decoded [`RedelegationResponse`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct RedelegationInfo {
+    pub delegator_address: String,
+    pub validator_src_address: String,
+    pub validator_dst_address: String,
+    pub entries: Vec<RedelegationEntryInfo>,
+}
+
+impl TryFrom<RedelegationResponse> for RedelegationInfo {
+    type Error = anyhow::Error;
+
+    fn try_from(response: RedelegationResponse) -> AnyResult<Self> {
+        let redelegation = response
+            .redelegation
+            .ok_or_any("No redelegation in response")?;
+
+        Ok(RedelegationInfo {
+            delegator_address: redelegation.delegator_address,
+            validator_src_address: redelegation.validator_src_address,
+            validator_dst_address: redelegation.validator_dst_address,
+            entries: response
+                .entries
+                .into_iter()
+                .map(|entry| {
+                    let redelegation_entry = entry
+                        .redelegation_entry
+                        .ok_or_any("No entry in redelegation response")?;
+
+                    Ok(RedelegationEntryInfo {
+                        creation_height: redelegation_entry.creation_height,
+                        completion_time: redelegation_entry.completion_time,
+                        initial_balance: Uint128::from_str(&redelegation_entry.initial_balance)?,
+                        shares_dst: sdk_dec_to_decimal256(&redelegation_entry.shares_dst)?,
+                        balance: Uint128::from_str(&entry.balance)?,
+                    })
+                })
+                .collect::<AnyResult<_>>()?,
+        })
+    }
+}
+
+/// Helpers for the `staking` module.
+impl GrpcClient {
+    /// List all the validators, optionally filtered by `status`.
+    pub async fn validators(&self, status: Option<BondStatus>) -> AnyResult<Vec<ValidatorInfo>> {
+        self.proto_query_all::<_, QueryValidatorsResponse>(
+            QueryValidatorsRequest {
+                status: status
+                    .map(|status| status.as_str_name().to_string())
+                    .unwrap_or_default(),
+                pagination: None,
+            },
+            "/cosmos.staking.v1beta1.Query/Validators",
+            PaginationOptions::default(),
+        )
+        .await?
+        .into_iter()
+        .map(ValidatorInfo::try_from)
+        .collect()
+    }
+
+    pub async fn validator(
+        &self,
+        validator_address: impl Into<String>,
+    ) -> AnyResult<ValidatorInfo> {
+        self.clients
+            .staking
+            .clone()
+            .validator(QueryValidatorRequest {
+                validator_addr: validator_address.into(),
+            })
+            .await?
+            .into_inner()
+            .validator
+            .ok_or_any("No validator in response")?
+            .try_into()
+    }
+
+    /// All the delegations of `delegator_address`.
+    pub async fn delegations(
+        &self,
+        delegator_address: impl Into<String>,
+    ) -> AnyResult<Vec<DelegationInfo>> {
+        self.proto_query_all::<_, QueryDelegatorDelegationsResponse>(
+            QueryDelegatorDelegationsRequest {
+                delegator_addr: delegator_address.into(),
+                pagination: None,
+            },
+            "/cosmos.staking.v1beta1.Query/DelegatorDelegations",
+            PaginationOptions::default(),
+        )
+        .await?
+        .into_iter()
+        .map(DelegationInfo::try_from)
+        .collect()
+    }
+
+    /// All the unbonding delegations of `delegator_address`.
+    pub async fn unbonding_delegations(
+        &self,
+        delegator_address: impl Into<String>,
+    ) -> AnyResult<Vec<UnbondingInfo>> {
+        self.proto_query_all::<_, QueryDelegatorUnbondingDelegationsResponse>(
+            QueryDelegatorUnbondingDelegationsRequest {
+                delegator_addr: delegator_address.into(),
+                pagination: None,
+            },
+            "/cosmos.staking.v1beta1.Query/DelegatorUnbondingDelegations",
+            PaginationOptions::default(),
+        )
+        .await?
+        .into_iter()
+        .map(UnbondingInfo::try_from)
+        .collect()
+    }
+
+    /// All the redelegations of `delegator_address`.
+    pub async fn redelegations(
+        &self,
+        delegator_address: impl Into<String>,
+    ) -> AnyResult<Vec<RedelegationInfo>> {
+        self.proto_query_all::<_, QueryRedelegationsResponse>(
+            QueryRedelegationsRequest {
+                delegator_addr: delegator_address.into(),
+                src_validator_addr: "".to_string(),
+                dst_validator_addr: "".to_string(),
+                pagination: None,
+            },
+            "/cosmos.staking.v1beta1.Query/Redelegations",
+            PaginationOptions::default(),
+        )
+        .await?
+        .into_iter()
+        .map(RedelegationInfo::try_from)
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::ValidatorInfo,
+        cosmos_sdk_proto::cosmos::staking::v1beta1::{
+            BondStatus, Commission, CommissionRates, Description, Validator,
+        },
+        cosmwasm_std::{Decimal, Decimal256, Uint128},
+        std::str::FromStr,
+    };
+
+    #[test]
+    fn validator_info() {
+        let validator = Validator {
+            operator_address: "osmovaloper1...".to_string(),
+            description: Some(Description {
+                moniker: "validator".to_string(),
+                ..Default::default()
+            }),
+            status: BondStatus::Bonded as i32,
+            tokens: "1000000".to_string(),
+            // 1_000_000.000000000000000001 shares, not representable as `Decimal`
+            delegator_shares: "1000000000000000000000001".to_string(),
+            commission: Some(Commission {
+                commission_rates: Some(CommissionRates {
+                    rate: "50000000000000000".to_string(),
+                    max_rate: "200000000000000000".to_string(),
+                    max_change_rate: "10000000000000000".to_string(),
+                }),
+                update_time: None,
+            }),
+            min_self_delegation: "1".to_string(),
+            ..Default::default()
+        };
+
+        let info = ValidatorInfo::try_from(validator).unwrap();
+
+        assert_eq!(info.moniker, "validator");
+        assert_eq!(info.status, BondStatus::Bonded);
+        assert_eq!(info.tokens, Uint128::new(1_000_000));
+        assert_eq!(
+            info.delegator_shares,
+            Decimal256::from_str("1000000.000000000000000001").unwrap()
+        );
+        assert_eq!(info.commission_rate, Decimal::percent(5));
+        assert_eq!(info.commission_max_rate, Decimal::percent(20));
+        assert_eq!(info.commission_max_change_rate, Decimal::percent(1));
+    }
+}
@@ -0,0 +1,409 @@
+use {
+    crate::AnyResult,
+    cosmos_sdk_proto::{
+        cosmos::{
+            authz::v1beta1 as authz,
+            bank::v1beta1 as bank,
+            base::query::v1beta1::{PageRequest, PageResponse},
+            distribution::v1beta1 as distribution,
+            feegrant::v1beta1 as feegrant,
+            gov::{v1 as gov_v1, v1beta1 as gov},
+            staking::v1beta1 as staking,
+        },
+        cosmwasm::wasm::v1 as wasm,
+    },
+    futures::{stream, Stream, TryStreamExt},
+    std::future::Future,
+};
+
+/// Request carrying a [`PageRequest`].
+pub trait PaginatedRequest: Clone {
+    fn set_pagination(&mut self, pagination: Option<PageRequest>);
+}
+
+/// Response carrying a [`PageResponse`] and a list of items.
+pub trait PaginatedResponse {
+    type Item;
+
+    fn pagination(&self) -> Option<&PageResponse>;
+
+    fn into_items(self) -> Vec<Self::Item>;
+}
+
+/// Options used to iterate over the pages of a query.
+#[derive(Clone, Debug, Default)]
+pub struct PaginationOptions {
+    /// Number of items requested for each page. If not set, the default limit of the node is used.
+    pub page_size: Option<u64>,
+    /// Iterate the items in descending order.
+    pub reverse: bool,
+    /// Use offset based pagination, starting from the given offset, instead of the `next_key` returned by the node.
+    pub offset: Option<u64>,
+}
+
+impl PaginationOptions {
+    pub fn page_size(mut self, page_size: u64) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    pub fn reverse(mut self, reverse: bool) -> Self {
+        self.reverse = reverse;
+        self
+    }
+
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    fn first_page(&self) -> PageRequest {
+        PageRequest {
+            key: vec![],
+            offset: self.offset.unwrap_or_default(),
+            limit: self.page_size.unwrap_or_default(),
+            count_total: false,
+            reverse: self.reverse,
+        }
+    }
+
+    fn next_page(
+        &self,
+        current: &PageRequest,
+        response: Option<&PageResponse>,
+        items: usize,
+    ) -> Option<PageRequest> {
+        let next_key = response.map(|res| res.next_key.clone()).unwrap_or_default();
+
+        if next_key.is_empty() || items == 0 {
+            return None;
+        }
+
+        match self.offset {
+            Some(_) => Some(PageRequest {
+                offset: current.offset + items as u64,
+                ..current.clone()
+            }),
+            None => Some(PageRequest {
+                key: next_key,
+                ..current.clone()
+            }),
+        }
+    }
+}
+
+/// Stream all the items of a paginated query, requesting a new page only when the previous one has been consumed.
+///
+/// `query` performs the request of a single page.
+/// ## Example:
+/// ``` ignore
+/// let bank = client.clients.bank.clone();
+///
+/// let balances = query_stream(
+///     QueryAllBalancesRequest {
+///         address: "osmo1...".to_string(),
+///         pagination: None,
+///     },
+///     PaginationOptions::default().page_size(100),
+///     move |request| {
+///         let mut bank = bank.clone();
+///         async move { Ok(bank.all_balances(request).await?.into_inner()) }
+///     },
+/// );
+/// ```
+pub fn query_stream<Req, Res, F, Fut>(
+    request: Req,
+    options: PaginationOptions,
+    query: F,
+) -> impl Stream<Item = AnyResult<Res::Item>>
+where
+    Req: PaginatedRequest,
+    Res: PaginatedResponse,
+    F: FnMut(Req) -> Fut,
+    Fut: Future<Output = AnyResult<Res>>,
+{
+    let first_page = options.first_page();
+
+    stream::try_unfold((query, Some(first_page)), move |(mut query, page)| {
+        let mut request = request.clone();
+        let options = options.clone();
+
+        async move {
+            let Some(page) = page else {
+                return Ok::<_, anyhow::Error>(None);
+            };
+
+            request.set_pagination(Some(page.clone()));
+
+            let response = query(request).await?;
+            let pagination = response.pagination().cloned();
+            let items = response.into_items();
+
+            let next = options.next_page(&page, pagination.as_ref(), items.len());
+
+            Ok(Some((items, (query, next))))
+        }
+    })
+    .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+    .try_flatten()
+}
+
+/// Collect all the items of a paginated query. See [`query_stream`].
+pub async fn query_all<Req, Res, F, Fut>(
+    request: Req,
+    options: PaginationOptions,
+    query: F,
+) -> AnyResult<Vec<Res::Item>>
+where
+    Req: PaginatedRequest,
+    Res: PaginatedResponse,
+    F: FnMut(Req) -> Fut,
+    Fut: Future<Output = AnyResult<Res>>,
+{
+    query_stream(request, options, query).try_collect().await
+}
+
+macro_rules! impl_paginated {
+    ($request:ty, $response:ty, $items:ident: $item:ty) => {
+        impl PaginatedRequest for $request {
+            fn set_pagination(&mut self, pagination: Option<PageRequest>) {
+                self.pagination = pagination;
+            }
+        }
+
+        impl PaginatedResponse for $response {
+            type Item = $item;
+
+            fn pagination(&self) -> Option<&PageResponse> {
+                self.pagination.as_ref()
+            }
+
+            fn into_items(self) -> Vec<$item> {
+                self.$items
+            }
+        }
+    };
+}
+
+// authz
+impl_paginated!(authz::QueryGrantsRequest, authz::QueryGrantsResponse, grants: authz::Grant);
+impl_paginated!(
+    authz::QueryGranterGrantsRequest,
+    authz::QueryGranterGrantsResponse,
+    grants: authz::GrantAuthorization
+);
+impl_paginated!(
+    authz::QueryGranteeGrantsRequest,
+    authz::QueryGranteeGrantsResponse,
+    grants: authz::GrantAuthorization
+);
+
+// bank
+impl_paginated!(
+    bank::QueryAllBalancesRequest,
+    bank::QueryAllBalancesResponse,
+    balances: cosmos_sdk_proto::cosmos::base::v1beta1::Coin
+);
+impl_paginated!(
+    bank::QuerySpendableBalancesRequest,
+    bank::QuerySpendableBalancesResponse,
+    balances: cosmos_sdk_proto::cosmos::base::v1beta1::Coin
+);
+impl_paginated!(
+    bank::QueryTotalSupplyRequest,
+    bank::QueryTotalSupplyResponse,
+    supply: cosmos_sdk_proto::cosmos::base::v1beta1::Coin
+);
+impl_paginated!(
+    bank::QueryDenomsMetadataRequest,
+    bank::QueryDenomsMetadataResponse,
+    metadatas: bank::Metadata
+);
+impl_paginated!(
+    bank::QueryDenomOwnersRequest,
+    bank::QueryDenomOwnersResponse,
+    denom_owners: bank::DenomOwner
+);
+
+// distribution
+impl_paginated!(
+    distribution::QueryValidatorSlashesRequest,
+    distribution::QueryValidatorSlashesResponse,
+    slashes: distribution::ValidatorSlashEvent
+);
+
+// feegrant
+impl_paginated!(
+    feegrant::QueryAllowancesRequest,
+    feegrant::QueryAllowancesResponse,
+    allowances: feegrant::Grant
+);
+impl_paginated!(
+    feegrant::QueryAllowancesByGranterRequest,
+    feegrant::QueryAllowancesByGranterResponse,
+    allowances: feegrant::Grant
+);
+
+// gov v1beta1
+impl_paginated!(gov::QueryProposalsRequest, gov::QueryProposalsResponse, proposals: gov::Proposal);
+impl_paginated!(gov::QueryVotesRequest, gov::QueryVotesResponse, votes: gov::Vote);
+impl_paginated!(gov::QueryDepositsRequest, gov::QueryDepositsResponse, deposits: gov::Deposit);
+
+// gov v1
+impl_paginated!(
+    gov_v1::QueryProposalsRequest,
+    gov_v1::QueryProposalsResponse,
+    proposals: gov_v1::Proposal
+);
+impl_paginated!(gov_v1::QueryVotesRequest, gov_v1::QueryVotesResponse, votes: gov_v1::Vote);
+impl_paginated!(
+    gov_v1::QueryDepositsRequest,
+    gov_v1::QueryDepositsResponse,
+    deposits: gov_v1::Deposit
+);
+
+// staking
+impl_paginated!(
+    staking::QueryValidatorsRequest,
+    staking::QueryValidatorsResponse,
+    validators: staking::Validator
+);
+impl_paginated!(
+    staking::QueryValidatorDelegationsRequest,
+    staking::QueryValidatorDelegationsResponse,
+    delegation_responses: staking::DelegationResponse
+);
+impl_paginated!(
+    staking::QueryValidatorUnbondingDelegationsRequest,
+    staking::QueryValidatorUnbondingDelegationsResponse,
+    unbonding_responses: staking::UnbondingDelegation
+);
+impl_paginated!(
+    staking::QueryDelegatorDelegationsRequest,
+    staking::QueryDelegatorDelegationsResponse,
+    delegation_responses: staking::DelegationResponse
+);
+impl_paginated!(
+    staking::QueryDelegatorUnbondingDelegationsRequest,
+    staking::QueryDelegatorUnbondingDelegationsResponse,
+    unbonding_responses: staking::UnbondingDelegation
+);
+impl_paginated!(
+    staking::QueryRedelegationsRequest,
+    staking::QueryRedelegationsResponse,
+    redelegation_responses: staking::RedelegationResponse
+);
+impl_paginated!(
+    staking::QueryDelegatorValidatorsRequest,
+    staking::QueryDelegatorValidatorsResponse,
+    validators: staking::Validator
+);
+
+// wasm
+impl_paginated!(
+    wasm::QueryContractsByCodeRequest,
+    wasm::QueryContractsByCodeResponse,
+    contracts: String
+);
+impl_paginated!(
+    wasm::QueryAllContractStateRequest,
+    wasm::QueryAllContractStateResponse,
+    models: wasm::Model
+);
+impl_paginated!(
+    wasm::QueryContractHistoryRequest,
+    wasm::QueryContractHistoryResponse,
+    entries: wasm::ContractCodeHistoryEntry
+);
+impl_paginated!(
+    wasm::QueryCodesRequest,
+    wasm::QueryCodesResponse,
+    code_infos: wasm::CodeInfoResponse
+);
+impl_paginated!(
+    wasm::QueryPinnedCodesRequest,
+    wasm::QueryPinnedCodesResponse,
+    code_ids: u64
+);
+
+#[cfg(test)]
+mod test {
+    use cosmos_sdk_proto::cosmos::base::query::v1beta1::{PageRequest, PageResponse};
+    use cosmos_sdk_proto::cosmwasm::wasm::v1::{
+        QueryContractsByCodeRequest, QueryContractsByCodeResponse,
+    };
+    use futures::StreamExt;
+
+    use super::{query_all, query_stream, PaginationOptions};
+
+    fn contracts_page(request: QueryContractsByCodeRequest) -> QueryContractsByCodeResponse {
+        let contracts = (0..10).map(|i| format!("contract_{i}")).collect::<Vec<_>>();
+        let page = request.pagination.unwrap();
+
+        let start = if page.key.is_empty() {
+            page.offset as usize
+        } else {
+            String::from_utf8(page.key).unwrap().parse().unwrap()
+        };
+        let end = (start + page.limit as usize).min(contracts.len());
+
+        QueryContractsByCodeResponse {
+            contracts: contracts[start..end].to_vec(),
+            pagination: Some(PageResponse {
+                next_key: if end < contracts.len() {
+                    end.to_string().into_bytes()
+                } else {
+                    vec![]
+                },
+                total: 0,
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn pagination() {
+        let request = QueryContractsByCodeRequest {
+            code_id: 1,
+            pagination: None,
+        };
+
+        let all = query_all(
+            request.clone(),
+            PaginationOptions::default().page_size(3),
+            |request| async move { Ok(contracts_page(request)) },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(all.len(), 10);
+        assert_eq!(all[9], "contract_9");
+
+        let from_offset = query_all(
+            request.clone(),
+            PaginationOptions::default().page_size(4).offset(5),
+            |request| async move { Ok(contracts_page(request)) },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(from_offset.first().unwrap(), "contract_5");
+        assert_eq!(from_offset.len(), 5);
+
+        let mut requested_pages: Vec<PageRequest> = vec![];
+
+        let first_two = query_stream(
+            request,
+            PaginationOptions::default().page_size(1),
+            |request| {
+                requested_pages.push(request.pagination.clone().unwrap());
+                async move { Ok(contracts_page(request)) }
+            },
+        )
+        .take(2)
+        .collect::<Vec<_>>()
+        .await;
+
+        assert_eq!(first_two.len(), 2);
+        assert_eq!(requested_pages.len(), 2);
+    }
+}
@@ -0,0 +1,185 @@
+use {
+    crate::{
+        errors::{Error, ErrorExt},
+        AnyResult,
+    },
+    cosmos_sdk_proto::cosmos::tx::v1beta1::BroadcastTxResponse,
+    std::time::Duration,
+};
+
+/// ABCI code returned by cosmos-sdk (`sdk` codespace) for a wrong account sequence.
+pub const SEQUENCE_MISMATCH_CODE: u32 = 32;
+
+/// How many times a tx rejected for an account sequence mismatch is signed again and broadcasted.
+#[derive(Clone, Copy, Debug)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    /// Time waited before each retry.
+    pub backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 3,
+            backoff: Duration::from_millis(500),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Never retry.
+    pub fn disabled() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 0,
+            backoff: Duration::ZERO,
+        }
+    }
+}
+
+/// Check if a log or an error message reports an account sequence mismatch.
+pub fn is_sequence_mismatch(message: &str) -> bool {
+    message.contains("account sequence mismatch") || message.contains("incorrect account sequence")
+}
+
+/// Parse the sequence expected by the chain from a sequence mismatch log,
+/// e.g. `account sequence mismatch, expected 10, got 9: incorrect account sequence`.
+pub fn parse_expected_sequence(message: &str) -> Option<u64> {
+    let (_, tail) = message.split_once("expected ")?;
+
+    tail.chars()
+        .take_while(|char| char.is_ascii_digit())
+        .collect::<String>()
+        .parse()
+        .ok()
+}
+
+/// Return the log of a broadcast result rejected for an account sequence mismatch,
+/// both in `CheckTx` and in the simulation.
+pub(crate) fn sequence_mismatch_log(result: &AnyResult<BroadcastTxResponse>) -> Option<String> {
+    match result {
+        Ok(res) => res
+            .tx_response
+            .as_ref()
+            .and_then(Error::from_tx_response)
+            .filter(Error::is_sequence_mismatch)
+            .map(|err| err.to_string()),
+        Err(err) => err.is_sequence_mismatch().then(|| format!("{err:#}")),
+    }
+}
+
+/// Check if the tx has been accepted in the mempool, consuming the account sequence.
+pub(crate) fn passed_check_tx(response: &BroadcastTxResponse) -> bool {
+    match &response.tx_response {
+        Some(res) => res.code == 0,
+        None => true,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::result_large_err)]
+mod test {
+    use {
+        super::{is_sequence_mismatch, parse_expected_sequence, RetryPolicy},
+        crate::{
+            mock::{broadcast_response, decode_tx, mock_fee, mock_wallet},
+            BroadcastMode, ProstMsgNameToAny, TxOptions,
+        },
+        cosmos_sdk_proto::cosmos::{bank::v1beta1::MsgSend, tx::v1beta1::BroadcastTxRequest},
+        prost::Message,
+        std::{
+            sync::{Arc, Mutex},
+            time::{Duration, Instant},
+        },
+        tonic::Status,
+    };
+
+    #[test]
+    fn sequence_mismatch() {
+        let log = "account sequence mismatch, expected 10, got 9: incorrect account sequence";
+
+        assert!(is_sequence_mismatch(log));
+        assert_eq!(parse_expected_sequence(log), Some(10));
+
+        assert!(!is_sequence_mismatch(
+            "out of gas in location: WritePerByte"
+        ));
+        assert_eq!(parse_expected_sequence("insufficient fees"), None);
+    }
+
+    #[tokio::test]
+    async fn wallet_retry() {
+        // Sequences of the broadcasted txs and sequence expected by the chain, `None` if always ahead
+        let sent = Arc::new(Mutex::new(vec![]));
+        let expected = Arc::new(Mutex::new(Some(5)));
+
+        let (node_sent, node_expected) = (sent.clone(), expected.clone());
+        let mut wallet = mock_wallet(move |path, _, request| match path {
+            "/cosmos.tx.v1beta1.Service/BroadcastTx" => {
+                let (_, sequence) = decode_tx(&BroadcastTxRequest::decode(request).unwrap().tx_bytes);
+                node_sent.lock().unwrap().push(sequence);
+
+                let expected = node_expected.lock().unwrap().unwrap_or(sequence + 1);
+
+                if sequence == expected {
+                    Ok(broadcast_response("ABC", 0, ""))
+                } else {
+                    Ok(broadcast_response(
+                        "",
+                        32,
+                        &format!("account sequence mismatch, expected {expected}, got {sequence}: incorrect account sequence"),
+                    ))
+                }
+            }
+            _ => Err(Status::unimplemented(path.to_string())),
+        })
+        .await;
+
+        wallet.sequence_retry = RetryPolicy {
+            max_retries: 2,
+            backoff: Duration::from_millis(50),
+        };
+
+        let msgs = vec![MsgSend {
+            from_address: wallet.account_address.clone(),
+            to_address: wallet.account_address.clone(),
+            amount: vec![],
+        }
+        .build_any()];
+
+        // Signed again with the expected sequence
+        let response = wallet
+            .broadcast_tx(
+                msgs.clone(),
+                Some(mock_fee()),
+                None,
+                TxOptions::default(),
+                BroadcastMode::Sync,
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.tx_response.unwrap().code, 0);
+        assert_eq!(*sent.lock().unwrap(), vec![0, 5]);
+        assert_eq!(wallet.account_sequence, 6);
+
+        // Gives up after `max_retries`, waiting `backoff` before each retry
+        sent.lock().unwrap().clear();
+        *expected.lock().unwrap() = None;
+
+        let start = Instant::now();
+        let response = wallet
+            .broadcast_tx(
+                msgs,
+                Some(mock_fee()),
+                None,
+                TxOptions::default(),
+                BroadcastMode::Sync,
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.tx_response.unwrap().code, 32);
+        assert_eq!(*sent.lock().unwrap(), vec![6, 7, 8]);
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        assert_eq!(wallet.account_sequence, 8);
+    }
+}
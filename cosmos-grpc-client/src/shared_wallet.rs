@@ -0,0 +1,402 @@
+use {
+    crate::{
+        definitions::BroadcastMode,
+        errors::ErrorExt,
+        fee::FeeStrategy,
+        sequence::{parse_expected_sequence, passed_check_tx, sequence_mismatch_log},
+        traits::SharedAny,
+        tx_options::TxOptions,
+        wallet::Wallet,
+        AnyResult,
+    },
+    cosmos_sdk_proto::cosmos::tx::v1beta1::{BroadcastTxResponse, SimulateResponse},
+    cosmrs::tx::Fee,
+    std::sync::Arc,
+    tokio::sync::Mutex,
+};
+
+#[derive(Clone, Copy, Debug)]
+struct Account {
+    number: u64,
+    sequence: u64,
+}
+
+/// Cloneable handle over a [`Wallet`] that can be used from many tasks at the same time.
+///
+/// Simulations run in parallel, while signing and broadcasting are serialized so that each tx
+/// gets the next account sequence. The sequence is incremented only if the tx passes `CheckTx`,
+/// and is synced with the chain on account sequence mismatch.
+///
+/// ## Example:
+/// ``` ignore
+/// let wallet = SharedWallet::new(wallet);
+///
+/// for msg in msgs {
+///     let wallet = wallet.clone();
+///     tokio::spawn(async move {
+///         wallet
+///             .broadcast_tx(vec![msg], None, None, TxOptions::default(), BroadcastMode::Sync)
+///             .await
+///     });
+/// }
+/// ```
+#[derive(Clone)]
+pub struct SharedWallet {
+    wallet: Arc<Wallet>,
+    account: Arc<Mutex<Account>>,
+}
+
+impl SharedWallet {
+    pub fn new(wallet: Wallet) -> SharedWallet {
+        let account = Account {
+            number: wallet.account_number,
+            sequence: wallet.account_sequence,
+        };
+
+        SharedWallet {
+            wallet: Arc::new(wallet),
+            account: Arc::new(Mutex::new(account)),
+        }
+    }
+
+    /// The inner [`Wallet`]. Its `account_number` and `account_sequence` are the ones at creation,
+    /// use [`SharedWallet::account_sequence`] for the current values.
+    pub fn wallet(&self) -> &Wallet {
+        &self.wallet
+    }
+
+    pub fn account_address(&self) -> &str {
+        &self.wallet.account_address
+    }
+
+    /// Sequence that will be used by the next tx.
+    pub async fn account_sequence(&self) -> u64 {
+        self.account.lock().await.sequence
+    }
+
+    /// Query the account from the auth module and update the account number and sequence.
+    pub async fn refresh_account(&self) -> AnyResult<()> {
+        let mut account = self.account.lock().await;
+        self.sync_account(&mut account, None).await
+    }
+
+    pub async fn simulate_tx(
+        &self,
+        msgs: Vec<impl SharedAny>,
+        tx_options: TxOptions,
+    ) -> AnyResult<SimulateResponse> {
+        let tx_options = tx_options.resolve(&self.wallet.client).await?;
+        let account = *self.account.lock().await;
+
+        self.wallet
+            .simulate_tx_for(
+                msgs,
+                None,
+                &tx_options,
+                self.wallet.fee_strategy.as_ref(),
+                account.number,
+                account.sequence,
+            )
+            .await
+    }
+
+    /// Sign and broadcast a tx. See [`Wallet::broadcast_tx`].
+    pub async fn broadcast_tx(
+        &self,
+        msgs: Vec<impl SharedAny>,
+        fee: Option<Fee>,
+        memo: Option<String>,
+        tx_options: TxOptions,
+        broadacast_mode: BroadcastMode,
+    ) -> AnyResult<BroadcastTxResponse> {
+        match fee {
+            Some(fee) => {
+                self.broadcast_tx_with_fee(msgs, &fee, memo, tx_options, broadacast_mode)
+                    .await
+            }
+            None => {
+                self.broadcast_tx_with_fee(
+                    msgs,
+                    self.wallet.fee_strategy.as_ref(),
+                    memo,
+                    tx_options,
+                    broadacast_mode,
+                )
+                .await
+            }
+        }
+    }
+
+    /// Sign and broadcast a tx, computing the fee with `fee_strategy`. See [`Wallet::broadcast_tx_with_fee`].
+    ///
+    /// If required by the strategy, the tx is simulated before acquiring the account sequence.
+    pub async fn broadcast_tx_with_fee(
+        &self,
+        msgs: Vec<impl SharedAny>,
+        fee_strategy: &dyn FeeStrategy,
+        memo: Option<String>,
+        tx_options: TxOptions,
+        broadacast_mode: BroadcastMode,
+    ) -> AnyResult<BroadcastTxResponse> {
+        let tx_options = tx_options.resolve(&self.wallet.client).await?;
+        let policy = self.wallet.sequence_retry;
+        let mut retries = 0;
+
+        let fee = loop {
+            let account = *self.account.lock().await;
+
+            match self
+                .wallet
+                .compute_fee(
+                    msgs.clone(),
+                    fee_strategy,
+                    memo.clone(),
+                    &tx_options,
+                    account.number,
+                    account.sequence,
+                )
+                .await
+            {
+                Ok(fee) => break fee,
+                Err(err) if retries < policy.max_retries && err.is_sequence_mismatch() => {
+                    retries += 1;
+
+                    let mut current = self.account.lock().await;
+
+                    // The sequence has been already updated by a concurrent broadcast
+                    if current.sequence == account.sequence {
+                        self.sync_account(&mut current, Some(&format!("{err:#}")))
+                            .await?;
+                    }
+
+                    drop(current);
+
+                    tokio::time::sleep(policy.backoff).await;
+                }
+                Err(err) => return Err(err),
+            }
+        };
+
+        let mode = broadacast_mode.repr();
+        let mut retries = 0;
+
+        loop {
+            let mut account = self.account.lock().await;
+
+            let res = self
+                .wallet
+                .sign_and_broadcast(
+                    msgs.clone(),
+                    fee.clone(),
+                    memo.clone(),
+                    &tx_options,
+                    mode,
+                    account.number,
+                    account.sequence,
+                )
+                .await;
+
+            match sequence_mismatch_log(&res) {
+                Some(log) if retries < policy.max_retries => {
+                    retries += 1;
+
+                    self.sync_account(&mut account, Some(&log)).await?;
+
+                    // Release the account during the backoff, so the other tasks can keep broadcasting
+                    drop(account);
+
+                    tokio::time::sleep(policy.backoff).await;
+                }
+                _ => {
+                    let res = res?;
+
+                    if passed_check_tx(&res) && !tx_options.unordered {
+                        account.sequence += 1;
+                    }
+
+                    return Ok(res);
+                }
+            }
+        }
+    }
+
+    /// Use the sequence expected by the chain if reported in `log`, otherwise query the account.
+    async fn sync_account(&self, account: &mut Account, log: Option<&str>) -> AnyResult<()> {
+        match log.and_then(parse_expected_sequence) {
+            Some(sequence) => account.sequence = sequence,
+            None => {
+                let (number, sequence) = self.wallet.query_own_account().await?;
+                account.number = number;
+                account.sequence = sequence;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl From<Wallet> for SharedWallet {
+    fn from(wallet: Wallet) -> Self {
+        SharedWallet::new(wallet)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::result_large_err)]
+mod test {
+    use {
+        super::SharedWallet,
+        crate::{
+            mock::{broadcast_response, decode_tx, mock_fee, mock_wallet, simulate_response},
+            BroadcastMode, ProstMsgNameToAny, RetryPolicy, TxOptions,
+        },
+        cosmos_sdk_proto::cosmos::{
+            bank::v1beta1::MsgSend,
+            tx::v1beta1::{BroadcastTxRequest, SimulateRequest},
+        },
+        prost::Message,
+        std::{
+            sync::{Arc, Mutex},
+            time::Duration,
+        },
+        tonic::Status,
+    };
+
+    /// Sequence of the account on the chain and sequences of the accepted txs.
+    #[derive(Default)]
+    struct Chain {
+        sequence: u64,
+        accepted: Vec<u64>,
+        simulations: usize,
+    }
+
+    #[tokio::test]
+    async fn concurrent_broadcasts() {
+        let chain = Arc::new(Mutex::new(Chain::default()));
+
+        let node = chain.clone();
+        let mut wallet = mock_wallet(move |path, _, request| {
+            let mut chain = node.lock().unwrap();
+
+            match path {
+                "/cosmos.tx.v1beta1.Service/Simulate" => {
+                    let (_, sequence) = decode_tx(&SimulateRequest::decode(request).unwrap().tx_bytes);
+                    chain.simulations += 1;
+
+                    if sequence != chain.sequence {
+                        return Err(Status::unknown(format!(
+                            "account sequence mismatch, expected {}, got {sequence}: incorrect account sequence",
+                            chain.sequence
+                        )));
+                    }
+
+                    Ok(simulate_response(100_000))
+                }
+                "/cosmos.tx.v1beta1.Service/BroadcastTx" => {
+                    let (body, sequence) = decode_tx(&BroadcastTxRequest::decode(request).unwrap().tx_bytes);
+
+                    if sequence != chain.sequence {
+                        return Ok(broadcast_response(
+                            "",
+                            32,
+                            &format!(
+                                "account sequence mismatch, expected {}, got {sequence}: incorrect account sequence",
+                                chain.sequence
+                            ),
+                        ));
+                    }
+
+                    if body.memo == "fail" {
+                        return Ok(broadcast_response("", 13, "insufficient fee"));
+                    }
+
+                    chain.sequence += 1;
+                    chain.accepted.push(sequence);
+
+                    Ok(broadcast_response("ABC", 0, ""))
+                }
+                _ => Err(Status::unimplemented(path.to_string())),
+            }
+        })
+        .await;
+
+        wallet.sequence_retry = RetryPolicy {
+            max_retries: 3,
+            backoff: Duration::from_millis(10),
+        };
+
+        let msgs = vec![MsgSend {
+            from_address: wallet.account_address.clone(),
+            to_address: wallet.account_address.clone(),
+            amount: vec![],
+        }
+        .build_any()];
+
+        let wallet = SharedWallet::new(wallet);
+
+        let broadcast = |memo: &str| {
+            let wallet = wallet.clone();
+            let msgs = msgs.clone();
+            let memo = memo.to_string();
+
+            async move {
+                wallet
+                    .broadcast_tx(
+                        msgs,
+                        Some(mock_fee()),
+                        Some(memo),
+                        TxOptions::default(),
+                        BroadcastMode::Sync,
+                    )
+                    .await
+                    .unwrap()
+                    .tx_response
+                    .unwrap()
+            }
+        };
+
+        // Each concurrent tx gets the next sequence
+        let tasks = (0..10)
+            .map(|_| tokio::spawn(broadcast("")))
+            .collect::<Vec<_>>();
+
+        for task in tasks {
+            assert_eq!(task.await.unwrap().code, 0);
+        }
+
+        assert_eq!(chain.lock().unwrap().accepted, (0..10).collect::<Vec<_>>());
+        assert_eq!(wallet.account_sequence().await, 10);
+
+        // A tx failed in `CheckTx` doesn't consume the sequence
+        assert_eq!(broadcast("fail").await.code, 13);
+        assert_eq!(wallet.account_sequence().await, 10);
+
+        assert_eq!(broadcast("").await.code, 0);
+        assert_eq!(chain.lock().unwrap().accepted[10], 10);
+
+        // Txs sent by another client, the sequence is synced from the mismatch log
+        chain.lock().unwrap().sequence = 15;
+
+        assert_eq!(broadcast("").await.code, 0);
+        assert_eq!(chain.lock().unwrap().accepted[11], 15);
+        assert_eq!(wallet.account_sequence().await, 16);
+
+        // Same in the simulation
+        chain.lock().unwrap().sequence = 20;
+
+        wallet
+            .broadcast_tx(
+                msgs.clone(),
+                None,
+                None,
+                TxOptions::default(),
+                BroadcastMode::Sync,
+            )
+            .await
+            .unwrap();
+
+        let chain = chain.lock().unwrap();
+        assert_eq!(chain.accepted[12], 20);
+        assert_eq!(chain.simulations, 2);
+    }
+}
@@ -1,4 +1,10 @@
-use {crate::AnyResult, anyhow::anyhow, cosmrs::Any, std::fmt::Display};
+use {
+    crate::{channel::BLOCK_HEIGHT_METADATA, AnyResult},
+    anyhow::anyhow,
+    cosmrs::Any,
+    std::fmt::Display,
+    tonic::metadata::MetadataMap,
+};
 
 pub trait IntoSerdeSerialize: serde::Serialize {
     fn json_serialize(&self) -> AnyResult<Vec<u8>> {
@@ -94,3 +100,95 @@ impl<T: prost::Message + prost::Name> ProstMsgNameToAny for T {
         }
     }
 }
+
+/// Path of the grpc query served for a request, used by [`GrpcClient::proto_query_named`](crate::GrpcClient::proto_query_named).
+///
+/// Implemented for the query requests of `cosmos_sdk_proto` with a [`prost::Name`].
+/// Other requests can opt in, naming the query explicitly or with [`query_path_from_name`]:
+/// ``` ignore
+/// impl QueryPath for PoolRequest {
+///     fn query_path() -> String {
+///         "/osmosis.poolmanager.v1beta1.Query/Pool".to_string()
+///     }
+/// }
+/// ```
+pub trait QueryPath {
+    fn query_path() -> String;
+}
+
+/// Path of a query following the cosmos-sdk convention:
+/// `{package}.Query{Method}Request` is served at `/{package}.Query/{Method}`.
+///
+/// Only valid for the requests of a `Query` service, not for messages or requests of other services.
+pub fn query_path_from_name<T: prost::Name>() -> String {
+    let method = T::NAME.strip_prefix("Query").unwrap_or(T::NAME);
+    let method = method.strip_suffix("Request").unwrap_or(method);
+
+    format!("/{}.Query/{method}", T::PACKAGE)
+}
+
+macro_rules! impl_query_path {
+    ($($request:ty),* $(,)?) => {
+        $(
+            impl QueryPath for $request {
+                fn query_path() -> String {
+                    query_path_from_name::<$request>()
+                }
+            }
+        )*
+    };
+}
+
+impl_query_path!(
+    cosmos_sdk_proto::cosmos::slashing::v1beta1::QueryParamsRequest,
+    cosmos_sdk_proto::cosmos::slashing::v1beta1::QuerySigningInfoRequest,
+    cosmos_sdk_proto::cosmos::slashing::v1beta1::QuerySigningInfosRequest,
+    cosmos_sdk_proto::cosmos::staking::v1beta1::QueryHistoricalInfoRequest,
+    cosmos_sdk_proto::cosmos::staking::v1beta1::QueryValidatorRequest,
+    cosmos_sdk_proto::cosmos::staking::v1beta1::QueryValidatorsRequest,
+);
+
+/// Read the height of the state a grpc response has been read from (`x-cosmos-block-height` metadata).
+pub trait BlockHeight {
+    fn block_height(&self) -> Option<u64>;
+}
+
+impl BlockHeight for MetadataMap {
+    fn block_height(&self) -> Option<u64> {
+        self.get(BLOCK_HEIGHT_METADATA)?.to_str().ok()?.parse().ok()
+    }
+}
+
+impl<T> BlockHeight for tonic::Response<T> {
+    fn block_height(&self) -> Option<u64> {
+        self.metadata().block_height()
+    }
+}
+
+impl BlockHeight for tonic::Status {
+    fn block_height(&self) -> Option<u64> {
+        self.metadata().block_height()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cosmos_sdk_proto::cosmos::{
+        slashing::v1beta1::QuerySigningInfosRequest, staking::v1beta1::QueryValidatorRequest,
+    };
+
+    use super::QueryPath;
+
+    #[test]
+    fn query_path() {
+        assert_eq!(
+            QueryValidatorRequest::query_path(),
+            "/cosmos.staking.v1beta1.Query/Validator"
+        );
+
+        assert_eq!(
+            QuerySigningInfosRequest::query_path(),
+            "/cosmos.slashing.v1beta1.Query/SigningInfos"
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use {
+    crate::{client::GrpcClient, AnyResult},
+    anyhow::bail,
+    prost_types::{Any, Timestamp},
+    std::time::SystemTime,
+};
+
+/// `cosmos.tx.v1beta1.TxBody`, including the fields added by sdk 0.53 (`unordered`, `timeout_timestamp`).
+///
+/// Empty fields are not encoded, so the body is still accepted by older chains.
+#[derive(Clone, PartialEq, prost::Message)]
+pub(crate) struct TxBody {
+    #[prost(message, repeated, tag = "1")]
+    pub messages: Vec<Any>,
+    #[prost(string, tag = "2")]
+    pub memo: String,
+    #[prost(uint64, tag = "3")]
+    pub timeout_height: u64,
+    #[prost(bool, tag = "4")]
+    pub unordered: bool,
+    #[prost(message, optional, tag = "5")]
+    pub timeout_timestamp: Option<Timestamp>,
+    #[prost(message, repeated, tag = "1023")]
+    pub extension_options: Vec<Any>,
+    #[prost(message, repeated, tag = "2047")]
+    pub non_critical_extension_options: Vec<Any>,
+}
+
+/// Options of the body of a tx.
+///
+/// ## Example:
+/// ``` ignore
+/// // The tx is valid for the next 10 blocks
+/// let options = TxOptions::default().timeout_blocks(10);
+///
+/// wallet
+///     .broadcast_tx(msgs, None, None, options, BroadcastMode::Sync)
+///     .await?;
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TxOptions {
+    /// Height after which the tx can't be included in a block.
+    pub timeout_height: Option<u64>,
+    /// Number of blocks, after the latest one, the tx can be included in.
+    /// Converted into `timeout_height` by [`TxOptions::resolve`].
+    pub timeout_blocks: Option<u64>,
+    /// Time after which the tx can't be included in a block. Required for unordered txs.
+    pub timeout_timestamp: Option<SystemTime>,
+    /// Unordered tx (sdk 0.53): the account sequence is neither checked nor incremented.
+    pub unordered: bool,
+    pub extension_options: Vec<Any>,
+    pub non_critical_extension_options: Vec<Any>,
+}
+
+impl TxOptions {
+    pub fn timeout_height(mut self, height: u64) -> Self {
+        self.timeout_height = Some(height);
+        self
+    }
+
+    pub fn timeout_blocks(mut self, blocks: u64) -> Self {
+        self.timeout_blocks = Some(blocks);
+        self
+    }
+
+    pub fn timeout_timestamp(mut self, timestamp: SystemTime) -> Self {
+        self.timeout_timestamp = Some(timestamp);
+        self
+    }
+
+    /// Broadcast an unordered tx, expiring at `timeout_timestamp`.
+    pub fn unordered(mut self, timeout_timestamp: SystemTime) -> Self {
+        self.unordered = true;
+        self.timeout_timestamp = Some(timeout_timestamp);
+        self
+    }
+
+    pub fn extension_option(mut self, option: Any) -> Self {
+        self.extension_options.push(option);
+        self
+    }
+
+    pub fn non_critical_extension_option(mut self, option: Any) -> Self {
+        self.non_critical_extension_options.push(option);
+        self
+    }
+
+    /// Convert `timeout_blocks` into `timeout_height`, using the latest height of the chain.
+    pub async fn resolve(mut self, client: &GrpcClient) -> AnyResult<TxOptions> {
+        if let Some(blocks) = self.timeout_blocks.take() {
+            let timeout_height = client.latest_height().await? + blocks;
+
+            self.timeout_height = Some(
+                self.timeout_height
+                    .map_or(timeout_height, |height| height.min(timeout_height)),
+            );
+        }
+
+        Ok(self)
+    }
+
+    pub(crate) fn tx_body(&self, messages: Vec<Any>, memo: Option<String>) -> AnyResult<TxBody> {
+        if self.timeout_blocks.is_some() {
+            bail!("Relative timeout not resolved, use TxOptions::resolve")
+        }
+
+        if self.unordered && self.timeout_timestamp.is_none() {
+            bail!("Unordered txs require a timeout_timestamp")
+        }
+
+        Ok(TxBody {
+            messages,
+            memo: memo.unwrap_or_default(),
+            timeout_height: self.timeout_height.unwrap_or_default(),
+            unordered: self.unordered,
+            timeout_timestamp: self.timeout_timestamp.map(Into::into),
+            extension_options: self.extension_options.clone(),
+            non_critical_extension_options: self.non_critical_extension_options.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::TxOptions,
+        prost::Message,
+        prost_types::Any,
+        std::time::{Duration, SystemTime},
+    };
+
+    #[test]
+    fn tx_body() {
+        let msg = Any {
+            type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+            value: vec![1, 2, 3],
+        };
+
+        let body = TxOptions::default()
+            .timeout_height(100)
+            .tx_body(vec![msg.clone()], Some("memo".to_string()))
+            .unwrap();
+
+        let expected = cosmrs::tx::BodyBuilder::new()
+            .msg(msg.clone())
+            .memo("memo")
+            .timeout_height(100_u32)
+            .finish()
+            .into_bytes()
+            .unwrap();
+
+        assert_eq!(body.encode_to_vec(), expected);
+
+        assert!(TxOptions::default()
+            .timeout_blocks(10)
+            .tx_body(vec![msg.clone()], None)
+            .is_err());
+
+        let mut unordered =
+            TxOptions::default().unordered(SystemTime::now() + Duration::from_secs(60));
+        assert!(unordered.tx_body(vec![msg.clone()], None).is_ok());
+
+        unordered.timeout_timestamp = None;
+        assert!(unordered.tx_body(vec![msg], None).is_err());
+    }
+}
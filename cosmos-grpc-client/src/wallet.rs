@@ -2,38 +2,38 @@ use {
     crate::{
         client::GrpcClient,
         definitions::BroadcastMode,
-        math::{IntoU64, IntoUint128},
-        traits::{IntoAnyhowResult, OkOrAny, SharedAny},
-        AnyResult, CoinType,
+        errors::Error,
+        fee::{FeeContext, FeeStrategy, SimulateAndAdjust},
+        gas_price::GasPriceOracle,
+        keys::{
+            derive_sign_key, generate_mnemonic, parse_private_key, private_key_to_hex, SeedOptions,
+        },
+        keystore::{key_type, Keystore},
+        modules::auth::decode_eth_account,
+        sequence::{parse_expected_sequence, passed_check_tx, sequence_mismatch_log, RetryPolicy},
+        traits::{IntoAnyhowResult, SharedAny},
+        tx_options::TxOptions,
+        AnyResult, KeyAlgorithm, KeyConfig,
     },
-    anyhow::anyhow,
+    anyhow::{anyhow, bail},
     bip32::secp256k1::{ecdsa::SigningKey, elliptic_curve::rand_core::OsRng},
     bip39::Mnemonic,
-    cosmos_sdk_proto::{
-        cosmos::{
-            auth::v1beta1::{BaseAccount, QueryAccountRequest},
-            tx::v1beta1::{
-                AuthInfo, BroadcastTxRequest, BroadcastTxResponse, SimulateRequest,
-                SimulateResponse, TxRaw,
-            },
-        },
-        traits::MessageExt,
+    cosmos_sdk_proto::cosmos::tx::v1beta1::{
+        AuthInfo, BroadcastTxRequest, BroadcastTxResponse, SimulateRequest, SimulateResponse, TxRaw,
     },
     cosmrs::{
         tx::{Fee, Raw, SignDoc, SignerInfo},
-        AccountId, Coin, Denom,
+        AccountId,
     },
-    cosmwasm_std::{Decimal, Uint128},
-    injective_protobuf::proto::account::EthAccount,
+    cosmwasm_std::Decimal,
     prost::Message as ProstMessage,
     prost_types::Any,
-    protobuf::Message as ProtoMessage,
     sha3::{Digest, Keccak256},
-    std::{fmt::Debug, str::FromStr},
+    std::{fmt::Debug, str::FromStr, sync::Arc},
 };
 #[non_exhaustive]
 pub struct Wallet {
-    coin_type: CoinType,
+    key_config: KeyConfig,
     pub account_address: String,
     pub sign_key: SigningKey,
     pub client: GrpcClient,
@@ -44,14 +44,26 @@ pub struct Wallet {
     pub gas_price: Decimal,
     pub gas_adjustment: Decimal,
     pub gas_denom: String,
+    /// Retry policy applied when a tx is rejected for an account sequence mismatch.
+    pub sequence_retry: RetryPolicy,
+    /// Strategy used to compute the fee when not provided. Default is [`SimulateAndAdjust`].
+    pub fee_strategy: Arc<dyn FeeStrategy>,
+    /// Oracle used to discover the gas price from the chain. See [`Wallet::fee_context`].
+    pub gas_price_oracle: Option<GasPriceOracle>,
+    /// Account paying the fee through a feegrant allowance, if not set by the [`FeeStrategy`].
+    pub fee_granter: Option<String>,
+    /// Account paying the fee, if not set by the [`FeeStrategy`].
+    /// The payer must sign the tx, so only the address of the wallet is accepted.
+    pub fee_payer: Option<String>,
 }
 
 #[allow(clippy::too_many_arguments)]
 impl Wallet {
+    /// Wallet with a random key, without a mnemonic. Use [`Wallet::generate`] to create a restorable wallet.
     pub async fn random(
         client: GrpcClient,
         chain_prefix: impl Into<String> + Clone,
-        coin_type: impl Into<u64> + Clone,
+        key_config: impl Into<KeyConfig>,
         gas_price: Decimal,
         gas_adjustment: Decimal,
         gas_denom: impl Into<String>,
@@ -62,7 +74,7 @@ impl Wallet {
             client,
             sign_key,
             chain_prefix,
-            coin_type,
+            key_config,
             gas_price,
             gas_adjustment,
             gas_denom,
@@ -70,29 +82,24 @@ impl Wallet {
         .await
     }
 
+    /// Wallet from a hex encoded private key, with or without the `0x` prefix.
     pub async fn from_private_key(
         client: GrpcClient,
         private_key: impl Into<String> + Clone,
         chain_prefix: impl Into<String> + Clone,
-        coin_type: impl Into<u64> + Clone,
+        key_config: impl Into<KeyConfig>,
         gas_price: Decimal,
         gas_adjustment: Decimal,
         gas_denom: impl Into<String>,
     ) -> AnyResult<Wallet> {
-        let private_key: String = private_key.into();
-
-        let sign_key = SigningKey::from_slice(
-            &private_key
-                .to_bytes()
-                .map_err(|err| anyhow!("Invalid private key, error: {err}"))?,
-        )
-        .into_anyresult()?;
+        let sign_key = parse_private_key(&private_key.into())
+            .map_err(|err| anyhow!("Invalid private key, error: {err}"))?;
 
         Wallet::finalize_wallet_creation(
             client,
             sign_key,
             chain_prefix,
-            coin_type,
+            key_config,
             gas_price,
             gas_adjustment,
             gas_denom,
@@ -104,28 +111,101 @@ impl Wallet {
         client: GrpcClient,
         seed_phrase: impl Into<String> + Clone,
         chain_prefix: impl Into<String> + Clone,
-        coin_type: impl Into<u64> + Clone,
-        account_index: u64,
+        key_config: impl Into<KeyConfig>,
+        seed_options: impl Into<SeedOptions>,
         gas_price: Decimal,
         gas_adjustment: Decimal,
         gas_denom: impl Into<String>,
     ) -> AnyResult<Wallet> {
-        let seed = Mnemonic::from_str(&seed_phrase.into())?.to_seed("");
+        let key_config: KeyConfig = key_config.into();
+        let mnemonic = Mnemonic::from_str(&seed_phrase.into())?;
+        let sign_key = derive_sign_key(&mnemonic, &seed_options.into(), key_config.coin_type)?;
 
-        let derivation_path = bip32::DerivationPath::from_str(&format!(
-            "m/44'/{}'/0'/0/{account_index}",
-            coin_type.clone().into()
-        ))?;
-        let sign_key = bip32::XPrv::derive_from_path(seed, &derivation_path)
-            .unwrap()
-            .private_key()
-            .clone();
+        Wallet::finalize_wallet_creation(
+            client,
+            sign_key,
+            chain_prefix,
+            key_config,
+            gas_price,
+            gas_adjustment,
+            gas_denom,
+        )
+        .await
+    }
+
+    /// Wallet from a new random mnemonic of `word_count` words (12 or 24), restorable in any cosmos wallet.
+    ///
+    /// The mnemonic is returned only here, store it safely.
+    /// ## Example:
+    /// ``` ignore
+    /// let (wallet, mnemonic) = Wallet::generate(
+    ///     client,
+    ///     24,
+    ///     "osmo",
+    ///     CoinType::Cosmos,
+    ///     SeedOptions::default().passphrase("secret"),
+    ///     gas_price,
+    ///     gas_adjustment,
+    ///     "uosmo",
+    /// )
+    /// .await?;
+    /// ```
+    pub async fn generate(
+        client: GrpcClient,
+        word_count: usize,
+        chain_prefix: impl Into<String> + Clone,
+        key_config: impl Into<KeyConfig>,
+        seed_options: impl Into<SeedOptions>,
+        gas_price: Decimal,
+        gas_adjustment: Decimal,
+        gas_denom: impl Into<String>,
+    ) -> AnyResult<(Wallet, String)> {
+        let key_config: KeyConfig = key_config.into();
+        let mnemonic = generate_mnemonic(word_count)?;
+        let sign_key = derive_sign_key(&mnemonic, &seed_options.into(), key_config.coin_type)?;
+
+        let wallet = Wallet::finalize_wallet_creation(
+            client,
+            sign_key,
+            chain_prefix,
+            key_config,
+            gas_price,
+            gas_adjustment,
+            gas_denom,
+        )
+        .await?;
+
+        Ok((wallet, mnemonic.to_string()))
+    }
+
+    /// Wallet from the key `name` of `keystore`, decrypted with `password`.
+    ///
+    /// The type of the stored key must match the algorithm of `key_config`.
+    pub async fn from_keystore(
+        client: GrpcClient,
+        keystore: &Keystore,
+        name: &str,
+        password: &str,
+        chain_prefix: impl Into<String> + Clone,
+        key_config: impl Into<KeyConfig>,
+        gas_price: Decimal,
+        gas_adjustment: Decimal,
+        gas_denom: impl Into<String>,
+    ) -> AnyResult<Wallet> {
+        let key_config: KeyConfig = key_config.into();
+        let (sign_key, stored_type) = keystore.private_key(name, password)?;
+
+        let expected_type = key_type(&key_config.algorithm);
+
+        if stored_type != expected_type {
+            bail!("Key {name} is {stored_type}, expected {expected_type}")
+        }
 
         Wallet::finalize_wallet_creation(
             client,
             sign_key,
             chain_prefix,
-            coin_type,
+            key_config,
             gas_price,
             gas_adjustment,
             gas_denom,
@@ -133,68 +213,310 @@ impl Wallet {
         .await
     }
 
+    /// Encrypt the key of the wallet with `password` and store it in `keystore` as `name`.
+    pub fn save_to_keystore(
+        &self,
+        keystore: &Keystore,
+        name: &str,
+        password: &str,
+    ) -> AnyResult<()> {
+        keystore.add(name, &self.sign_key, &self.key_config.algorithm, password)
+    }
+
+    /// Hex encoded private key, accepted by [`Wallet::from_private_key`].
+    pub fn private_key_hex(&self) -> String {
+        private_key_to_hex(&self.sign_key)
+    }
+
+    /// Sign and broadcast a tx.
+    ///
+    /// If `fee` is `None`, the fee is computed by `fee_strategy`.
+    /// See [`Wallet::broadcast_tx_with_fee`].
     pub async fn broadcast_tx(
         &mut self,
         msgs: Vec<impl SharedAny>,
         fee: Option<Fee>,
         memo: Option<String>,
+        tx_options: TxOptions,
         broadacast_mode: BroadcastMode,
     ) -> AnyResult<BroadcastTxResponse> {
-        let fee = if let Some(fee) = fee {
-            fee
-        } else {
-            let gas_used = self
-                .simulate_tx(msgs.clone())
+        match fee {
+            Some(fee) => {
+                self.broadcast_tx_with_fee(msgs, &fee, memo, tx_options, broadacast_mode)
+                    .await
+            }
+            None => {
+                let fee_strategy = self.fee_strategy.clone();
+                self.broadcast_tx_with_fee(
+                    msgs,
+                    fee_strategy.as_ref(),
+                    memo,
+                    tx_options,
+                    broadacast_mode,
+                )
+                .await
+            }
+        }
+    }
+
+    /// Sign and broadcast a tx, computing the fee with `fee_strategy`.
+    ///
+    /// If the tx is rejected for an account sequence mismatch (code 32), the sequence is
+    /// synced with the chain and the tx is signed and broadcasted again, up to `sequence_retry.max_retries` times.
+    /// The local sequence is incremented only if the tx passes `CheckTx` and is not unordered.
+    /// ## Example:
+    /// ``` ignore
+    /// wallet
+    ///     .broadcast_tx_with_fee(
+    ///         msgs,
+    ///         &FixedGas(300_000),
+    ///         None,
+    ///         TxOptions::default(),
+    ///         BroadcastMode::Sync,
+    ///     )
+    ///     .await?;
+    /// ```
+    pub async fn broadcast_tx_with_fee(
+        &mut self,
+        msgs: Vec<impl SharedAny>,
+        fee_strategy: &dyn FeeStrategy,
+        memo: Option<String>,
+        tx_options: TxOptions,
+        broadacast_mode: BroadcastMode,
+    ) -> AnyResult<BroadcastTxResponse> {
+        let mode = broadacast_mode.repr();
+        let tx_options = tx_options.resolve(&self.client).await?;
+        let mut retries = 0;
+
+        loop {
+            let res = self
+                .try_broadcast_tx(msgs.clone(), fee_strategy, memo.clone(), &tx_options, mode)
+                .await;
+
+            match sequence_mismatch_log(&res) {
+                Some(log) if retries < self.sequence_retry.max_retries => {
+                    retries += 1;
+
+                    match parse_expected_sequence(&log) {
+                        Some(sequence) => self.account_sequence = sequence,
+                        None => self.refresh_account().await?,
+                    }
+
+                    tokio::time::sleep(self.sequence_retry.backoff).await;
+                }
+                _ => {
+                    let res = res?;
+
+                    if passed_check_tx(&res) && !tx_options.unordered {
+                        self.account_sequence += 1;
+                    }
+
+                    return Ok(res);
+                }
+            }
+        }
+    }
+
+    /// Coin type and signature scheme of the key.
+    pub fn key_config(&self) -> &KeyConfig {
+        &self.key_config
+    }
+
+    /// Query the account from the auth module and update `account_number` and `account_sequence`.
+    pub async fn refresh_account(&mut self) -> AnyResult<()> {
+        let (number, sequence) = self.query_own_account().await?;
+
+        self.account_number = number;
+        self.account_sequence = sequence;
+
+        Ok(())
+    }
+
+    pub async fn simulate_tx(
+        &self,
+        msgs: Vec<impl SharedAny>,
+        tx_options: TxOptions,
+    ) -> AnyResult<SimulateResponse> {
+        let tx_options = tx_options.resolve(&self.client).await?;
+
+        self.simulate_tx_for(
+            msgs,
+            None,
+            &tx_options,
+            self.fee_strategy.as_ref(),
+            self.account_number,
+            self.account_sequence,
+        )
+        .await
+    }
+
+    async fn try_broadcast_tx(
+        &self,
+        msgs: Vec<impl SharedAny>,
+        fee_strategy: &dyn FeeStrategy,
+        memo: Option<String>,
+        tx_options: &TxOptions,
+        mode: i32,
+    ) -> AnyResult<BroadcastTxResponse> {
+        let fee = self
+            .compute_fee(
+                msgs.clone(),
+                fee_strategy,
+                memo.clone(),
+                tx_options,
+                self.account_number,
+                self.account_sequence,
+            )
+            .await?;
+
+        self.sign_and_broadcast(
+            msgs,
+            fee,
+            memo,
+            tx_options,
+            mode,
+            self.account_number,
+            self.account_sequence,
+        )
+        .await
+    }
+
+    /// Values used by the [`FeeStrategy`].
+    ///
+    /// If `gas_price_oracle` is set, the gas price is the max between `gas_price` and the one of the oracle.
+    /// If the oracle has no source available, `gas_price` is used.
+    pub async fn fee_context(&self) -> FeeContext {
+        let gas_price = match &self.gas_price_oracle {
+            Some(oracle) => match oracle.gas_price().await {
+                Ok(price) => price.max(self.gas_price),
+                Err(_) => self.gas_price,
+            },
+            None => self.gas_price,
+        };
+
+        FeeContext {
+            gas_price,
+            gas_adjustment: self.gas_adjustment,
+            gas_denom: self.gas_denom.clone(),
+        }
+    }
+
+    /// Simulate the tx if required by `fee_strategy` and compute the fee.
+    pub(crate) async fn compute_fee(
+        &self,
+        msgs: Vec<impl SharedAny>,
+        fee_strategy: &dyn FeeStrategy,
+        memo: Option<String>,
+        tx_options: &TxOptions,
+        account_number: u64,
+        sequence: u64,
+    ) -> AnyResult<Fee> {
+        let (granter, payer) = self.fee_grant(fee_strategy)?;
+
+        let gas_used = if fee_strategy.needs_simulation() {
+            Some(
+                self.simulate_tx_for(
+                    msgs,
+                    memo,
+                    tx_options,
+                    fee_strategy,
+                    account_number,
+                    sequence,
+                )
                 .await?
                 .gas_info
                 .ok_or(anyhow!("No gas info in response"))?
-                .gas_used;
+                .gas_used,
+            )
+        } else {
+            None
+        };
 
-            Fee {
-                amount: vec![Coin {
-                    denom: Denom::from_str(&self.gas_denom).into_anyresult()?,
-                    amount: (self.gas_price * self.gas_adjustment * gas_used.as_uint128()
-                        + Uint128::one())
-                    .into(),
-                }],
-                gas_limit: (gas_used.as_uint128() * self.gas_adjustment - Uint128::one()).as_u64(),
-                payer: None,
-                granter: None,
+        let mut fee = fee_strategy.compute_fee(&self.fee_context().await, gas_used)?;
+
+        fee.granter = granter;
+        fee.payer = payer;
+
+        Ok(fee)
+    }
+
+    /// Fee granter and payer of `fee_strategy`, falling back to the ones of the wallet.
+    fn fee_grant(
+        &self,
+        fee_strategy: &dyn FeeStrategy,
+    ) -> AnyResult<(Option<AccountId>, Option<AccountId>)> {
+        let (granter, payer) = fee_strategy.fee_grant()?;
+
+        let granter = granter.or(parse_account_id(self.fee_granter.as_deref())?);
+        let payer = payer.or(parse_account_id(self.fee_payer.as_deref())?);
+
+        // The signature of the payer is required, the wallet can only sign for itself
+        if let Some(payer) = &payer {
+            if payer.as_ref() != self.account_address {
+                bail!(
+                    "Fee payer {payer} must sign the tx, only the wallet address {} is supported",
+                    self.account_address
+                )
             }
-        };
+        }
 
+        Ok((granter, payer))
+    }
+
+    pub(crate) async fn sign_and_broadcast(
+        &self,
+        msgs: Vec<impl SharedAny>,
+        fee: Fee,
+        memo: Option<String>,
+        tx_options: &TxOptions,
+        mode: i32,
+        account_number: u64,
+        sequence: u64,
+    ) -> AnyResult<BroadcastTxResponse> {
         let request = BroadcastTxRequest {
             tx_bytes: self
-                .create_tx(msgs, fee, memo)?
+                .create_tx_for(msgs, fee, memo, tx_options, account_number, sequence)?
                 .to_bytes()
                 .into_anyresult()?,
-            mode: broadacast_mode.repr(),
+            mode,
         };
 
-        let res = self
+        Ok(self
             .client
             .clients
             .tx
             .clone()
             .broadcast_tx(request)
-            .await?
-            .into_inner();
-
-        self.account_sequence += 1;
-        Ok(res)
+            .await
+            .map_err(Error::from_tx_status)?
+            .into_inner())
     }
 
     #[allow(deprecated)]
-    pub async fn simulate_tx(&self, msgs: Vec<impl SharedAny>) -> AnyResult<SimulateResponse> {
-        let tx = self.create_tx(
+    pub(crate) async fn simulate_tx_for(
+        &self,
+        msgs: Vec<impl SharedAny>,
+        memo: Option<String>,
+        tx_options: &TxOptions,
+        fee_strategy: &dyn FeeStrategy,
+        account_number: u64,
+        sequence: u64,
+    ) -> AnyResult<SimulateResponse> {
+        // The fee grant is used also in the simulation, consuming gas
+        let (granter, payer) = self.fee_grant(fee_strategy)?;
+
+        let tx = self.create_tx_for(
             msgs,
             Fee {
                 amount: vec![],
                 gas_limit: 0,
-                granter: None,
-                payer: None,
+                granter,
+                payer,
             },
-            Some("".to_string()),
+            memo,
+            tx_options,
+            account_number,
+            sequence,
         )?;
 
         let request = SimulateRequest {
@@ -208,78 +530,57 @@ impl Wallet {
             .tx
             .clone()
             .simulate(request)
-            .await?
+            .await
+            .map_err(Error::from_tx_status)?
             .into_inner())
     }
 
+    pub(crate) async fn query_own_account(&self) -> AnyResult<(u64, u64)> {
+        Wallet::query_account(
+            &self.client,
+            &self.account_address,
+            &self.key_config.algorithm,
+        )
+        .await
+    }
+
     async fn finalize_wallet_creation(
         client: GrpcClient,
         sign_key: SigningKey,
         chain_prefix: impl Into<String> + Clone,
-        coin_type: impl Into<u64> + Clone,
+        key_config: impl Into<KeyConfig>,
         gas_price: Decimal,
         gas_adjustment: Decimal,
         gas_denom: impl Into<String>,
     ) -> AnyResult<Wallet> {
-        let coin_type =
-            CoinType::from_repr(coin_type.into()).ok_or(anyhow!("Invalid coin type"))?;
+        let key_config: KeyConfig = key_config.into();
 
-        let account_address = match coin_type {
-            CoinType::Injective => {
+        let account_address = match key_config.algorithm {
+            KeyAlgorithm::EthSecp256k1(_) => {
                 let pk = sign_key.verifying_key();
                 let uncompressed_bytes = pk.to_encoded_point(false).to_bytes();
 
                 let mut hasher = Keccak256::new();
                 hasher.update(&uncompressed_bytes[1..]);
                 let address_bytes = hasher.finalize();
-                AccountId::new("inj", &address_bytes[12..])
-                    .unwrap()
+                AccountId::new(&chain_prefix.clone().into(), &address_bytes[12..])
+                    .into_anyresult()?
+                    .to_string()
+            }
+            KeyAlgorithm::Secp256k1 => {
+                cosmrs::crypto::secp256k1::SigningKey::new(Box::new(sign_key.clone()))
+                    .public_key()
+                    .account_id(&chain_prefix.clone().into())
+                    .into_anyresult()?
                     .to_string()
             }
-            _ => cosmrs::crypto::secp256k1::SigningKey::new(Box::new(sign_key.clone()))
-                .public_key()
-                .account_id(&chain_prefix.clone().into())
-                .into_anyresult()?
-                .to_string(),
-        };
-        let raw_res = client
-            .clients
-            .auth
-            .clone()
-            .account(QueryAccountRequest {
-                address: account_address.clone(),
-            })
-            .await
-            .map(|res| res.into_inner());
-
-        let (number, sequence) = match raw_res {
-            #[allow(clippy::match_single_binding)]
-            Ok(raw_res) => match coin_type {
-                CoinType::Injective => EthAccount::parse_from_bytes(
-                    raw_res
-                        .account
-                        .ok_or_any("Error unwrapping None in raw_res.account")?
-                        .value
-                        .as_slice(),
-                )?
-                .base_account
-                .map(|res| (res.account_number, res.sequence))
-                .unwrap_or((0, 0)),
-                _ => BaseAccount::decode(
-                    &raw_res
-                        .account
-                        .ok_or_any("Error unwrapping None in raw_res.account")?
-                        .value[..],
-                )
-                .map(|res| (res.account_number, res.sequence))
-                .unwrap_or((0, 0)),
-            },
-            Err(_) => (0, 0),
         };
+        let (number, sequence) =
+            Wallet::query_account(&client, &account_address, &key_config.algorithm).await?;
 
         Ok(Wallet {
             account_address,
-            coin_type,
+            key_config,
             client: client.clone(),
             chain_id: client.chain_id.clone(),
             prefix: chain_prefix.into(),
@@ -289,50 +590,95 @@ impl Wallet {
             gas_price,
             gas_adjustment,
             gas_denom: gas_denom.into(),
+            sequence_retry: RetryPolicy::default(),
+            fee_strategy: Arc::new(SimulateAndAdjust),
+            gas_price_oracle: None,
+            fee_granter: None,
+            fee_payer: None,
         })
     }
 
+    /// Account number and sequence of `account_address`, `(0, 0)` if the account doesn't exist yet.
+    async fn query_account(
+        client: &GrpcClient,
+        account_address: &str,
+        algorithm: &KeyAlgorithm,
+    ) -> AnyResult<(u64, u64)> {
+        let account = match client.account(account_address).await? {
+            Some(account) => match algorithm {
+                KeyAlgorithm::EthSecp256k1(eth_config)
+                    if account.type_url == eth_config.account_type_url =>
+                {
+                    decode_eth_account(&account.value)?
+                }
+                _ => client.account_decoder.decode(&account)?,
+            },
+            None => return Ok((0, 0)),
+        };
+
+        Ok((account.account_number, account.sequence))
+    }
+
+    /// Sign a tx. `timeout_blocks` of `tx_options` must be already resolved, see [`TxOptions::resolve`].
     pub fn create_tx(
         &self,
         msgs: Vec<impl SharedAny>,
         fee: Fee,
         memo: Option<String>,
+        tx_options: TxOptions,
     ) -> AnyResult<Raw> {
-        let tx_body = cosmrs::tx::BodyBuilder::new()
-            .msgs(
-                msgs.into_iter()
-                    .map(|val| val.into_any())
-                    .collect::<Vec<Any>>(),
-            )
-            .memo(memo.unwrap_or("".to_string()))
-            .finish();
-
-        let auth_info = SignerInfo::single_direct(
-            Some(self.sign_key.verifying_key().into()),
+        self.create_tx_for(
+            msgs,
+            fee,
+            memo,
+            &tx_options,
+            self.account_number,
             self.account_sequence,
         )
-        .auth_info(fee);
+    }
 
-        let mut sign_doc = SignDoc::new(
-            &tx_body,
-            &auth_info,
-            &self.chain_id.parse()?,
-            self.account_number,
-        )
-        .into_anyresult()?;
+    pub(crate) fn create_tx_for(
+        &self,
+        msgs: Vec<impl SharedAny>,
+        fee: Fee,
+        memo: Option<String>,
+        tx_options: &TxOptions,
+        account_number: u64,
+        sequence: u64,
+    ) -> AnyResult<Raw> {
+        let tx_body = tx_options.tx_body(
+            msgs.into_iter()
+                .map(|val| val.into_any())
+                .collect::<Vec<Any>>(),
+            memo,
+        )?;
+
+        let auth_info =
+            SignerInfo::single_direct(Some(self.sign_key.verifying_key().into()), sequence)
+                .auth_info(fee);
 
-        match self.coin_type {
-            CoinType::Injective => {
-                let mut auth_info = AuthInfo::decode(sign_doc.auth_info_bytes.as_slice()).unwrap();
+        let mut sign_doc = SignDoc {
+            body_bytes: tx_body.encode_to_vec(),
+            auth_info_bytes: auth_info.into_bytes().into_anyresult()?,
+            chain_id: self.chain_id.clone(),
+            account_number,
+        };
+
+        match &self.key_config.algorithm {
+            KeyAlgorithm::EthSecp256k1(eth_config) => {
+                let mut auth_info = AuthInfo::decode(sign_doc.auth_info_bytes.as_slice())?;
 
                 for signer_info in auth_info.signer_infos.iter_mut() {
-                    signer_info.public_key.as_mut().unwrap().type_url =
-                        "/injective.crypto.v1beta1.ethsecp256k1.PubKey".to_string()
+                    signer_info
+                        .public_key
+                        .as_mut()
+                        .ok_or(anyhow!("No public key in signer info"))?
+                        .type_url = eth_config.pubkey_type_url.clone()
                 }
 
                 sign_doc.auth_info_bytes = auth_info.encode_to_vec();
 
-                let data = sign_doc.clone().into_bytes().unwrap();
+                let data = sign_doc.clone().into_bytes().into_anyresult()?;
 
                 let digest = sha3::Keccak256::new_with_prefix(data);
 
@@ -345,7 +691,7 @@ impl Wallet {
                 }
                 .into())
             }
-            _ => sign_doc
+            KeyAlgorithm::Secp256k1 => sign_doc
                 .sign(&cosmrs::crypto::secp256k1::SigningKey::new(Box::new(
                     self.sign_key.clone(),
                 )))
@@ -354,6 +700,13 @@ impl Wallet {
     }
 }
 
+pub(crate) fn parse_account_id(address: Option<&str>) -> AnyResult<Option<AccountId>> {
+    address
+        .map(AccountId::from_str)
+        .transpose()
+        .into_anyresult()
+}
+
 impl Debug for Wallet {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Wallet")
@@ -365,6 +718,8 @@ impl Debug for Wallet {
             .field("gas_price", &format!("{}", &self.gas_price))
             .field("gas_adjustment", &format!("{}", &self.gas_adjustment))
             .field("gas_denom", &self.gas_denom)
+            .field("fee_granter", &self.fee_granter)
+            .field("fee_payer", &self.fee_payer)
             .finish()
     }
 }
@@ -379,7 +734,7 @@ mod test {
     use crate::{
         definitions::{INJECTIVE_GRPC_TESTNET, OSMOSIS_GRPC_TESTNET},
         traits::ProstMsgToAny,
-        CoinType, GrpcClient, ProstMsgNameToAny, Wallet,
+        CoinType, GrpcClient, KeyConfig, ProstMsgNameToAny, TxOptions, Wallet,
     };
 
     #[tokio::test]
@@ -412,7 +767,10 @@ mod test {
             }],
         };
 
-        wallet.simulate_tx(vec![msg.build_any()]).await.unwrap();
+        wallet
+            .simulate_tx(vec![msg.build_any()], TxOptions::default())
+            .await
+            .unwrap();
 
         let msg = cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend {
             from_address: wallet.account_address.clone(),
@@ -424,7 +782,10 @@ mod test {
         };
 
         wallet
-            .simulate_tx(vec![msg.build_any_with_type_url("type_url")])
+            .simulate_tx(
+                vec![msg.build_any_with_type_url("type_url")],
+                TxOptions::default(),
+            )
             .await
             .unwrap();
     }
@@ -461,7 +822,7 @@ mod test {
             client.clone(),
             seed,
             "inj",
-            CoinType::Injective,
+            KeyConfig::injective(),
             0,
             Decimal::from_str("700000000").unwrap(),
             Decimal::from_str("1.5").unwrap(),
@@ -487,6 +848,7 @@ mod test {
                 vec![msg.build_any()],
                 None,
                 None,
+                TxOptions::default(),
                 crate::BroadcastMode::Sync,
             )
             .await